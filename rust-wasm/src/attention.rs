@@ -0,0 +1,110 @@
+// ALiBi (Attention with Linear Biases) self-attention over the H-module's
+// buffered planning context, replacing the scalar `base_activation` sum with
+// attention-weighted aggregation. See Press et al., "Train Short, Test Long".
+
+/// Per-head ALiBi slopes following the geometric sequence `2^(-8/n), 2^(-16/n), ...`
+/// for `n` heads (e.g. 1/2, 1/4, 1/8, ... for n = 8).
+pub fn alibi_slopes(num_heads: usize) -> Vec<f32> {
+    if num_heads == 0 {
+        return Vec::new();
+    }
+    let ratio = 2f32.powf(-8.0 / num_heads as f32);
+    (1..=num_heads).map(|h| ratio.powi(h as i32)).collect()
+}
+
+/// Single-head scaled dot-product attention with an ALiBi distance bias,
+/// restricted to keys within `max_distance` of the query position.
+///
+/// `keys`/`values` hold one row per window position; `query` is the single
+/// query vector, placed at the last position; `slope` is this head's
+/// ALiBi slope `m`. Returns the attended output for the *last* position in
+/// the window, which is what planning cares about (the current step).
+pub fn alibi_attention(
+    keys: &[Vec<f32>],
+    values: &[Vec<f32>],
+    query: &[f32],
+    slope: f32,
+    max_distance: u32,
+) -> Vec<f32> {
+    let seq_len = keys.len();
+    if seq_len == 0 || query.is_empty() {
+        return Vec::new();
+    }
+
+    let dim = query.len();
+    let scale = 1.0 / (dim as f32).sqrt();
+    let query_pos = seq_len as i64 - 1;
+
+    let mut scores = Vec::with_capacity(seq_len);
+    for (key_pos, key) in keys.iter().enumerate() {
+        let distance = (query_pos - key_pos as i64).abs();
+        if distance as u32 > max_distance {
+            scores.push(f32::NEG_INFINITY);
+            continue;
+        }
+
+        let dot: f32 = query
+            .iter()
+            .zip(key.iter())
+            .map(|(q, k)| q * k)
+            .sum::<f32>()
+            * scale;
+        let bias = -slope * distance as f32;
+        scores.push(dot + bias);
+    }
+
+    softmax_weighted_sum(&scores, values, dim)
+}
+
+fn softmax_weighted_sum(scores: &[f32], values: &[Vec<f32>], dim: usize) -> Vec<f32> {
+    let max_score = scores.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+    if !max_score.is_finite() {
+        return vec![0.0; dim];
+    }
+
+    let exps: Vec<f32> = scores
+        .iter()
+        .map(|s| if s.is_finite() { (s - max_score).exp() } else { 0.0 })
+        .collect();
+    let sum: f32 = exps.iter().sum();
+    if sum <= f32::EPSILON {
+        return vec![0.0; dim];
+    }
+
+    let mut out = vec![0.0; dim];
+    for (weight, value) in exps.iter().zip(values.iter()) {
+        let w = weight / sum;
+        for (o, v) in out.iter_mut().zip(value.iter()) {
+            *o += w * v;
+        }
+    }
+    out
+}
+
+/// Multi-head ALiBi attention: runs one head per slope and averages the
+/// per-head outputs into a single aggregated context vector.
+pub fn multi_head_alibi(
+    keys: &[Vec<f32>],
+    values: &[Vec<f32>],
+    query: &[f32],
+    num_heads: usize,
+    max_distance: u32,
+) -> Vec<f32> {
+    let slopes = alibi_slopes(num_heads);
+    if slopes.is_empty() || query.is_empty() {
+        return vec![0.0; query.len()];
+    }
+
+    let dim = query.len();
+    let mut aggregate = vec![0.0f32; dim];
+    for slope in &slopes {
+        let head_out = alibi_attention(keys, values, query, *slope, max_distance);
+        for (a, h) in aggregate.iter_mut().zip(head_out.iter()) {
+            *a += h;
+        }
+    }
+    for a in aggregate.iter_mut() {
+        *a /= slopes.len() as f32;
+    }
+    aggregate
+}
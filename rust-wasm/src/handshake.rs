@@ -0,0 +1,152 @@
+// Versioned capability-negotiation handshake run before a desktop connection
+// is allowed to become `Connected`.
+
+use serde::{Deserialize, Serialize};
+
+/// Our advertised protocol/feature versions and capabilities, compared
+/// against the desktop's on `negotiate`.
+pub const PROTOCOL_VERSION: u16 = 1;
+pub const FEATURE_VERSION: u16 = 1;
+
+#[derive(Serialize, Deserialize)]
+pub struct RemoteVersion {
+    pub protocol_version: u16,
+    pub feature_version: u16,
+    pub capabilities: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct NegotiationAck {
+    pub status: &'static str, // "ack"
+    pub protocol_version: u16,
+    pub feature_version: u16,
+    pub capabilities: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NegotiationNack {
+    pub status: &'static str, // "nack"
+    pub reason: String,
+}
+
+/// Compare the desktop's advertised versions/capabilities against ours,
+/// returning either the agreed ACK (capability intersection and minimum
+/// common protocol/feature version) or a NACK explaining the incompatibility.
+pub fn negotiate(
+    our_protocol_version: u16,
+    our_feature_version: u16,
+    our_capabilities: &[String],
+    remote_version_json: &str,
+) -> Result<NegotiationAck, NegotiationNack> {
+    let remote: RemoteVersion = serde_json::from_str(remote_version_json)
+        .map_err(|e| nack(format!("invalid version payload: {}", e)))?;
+
+    if remote.protocol_version == 0 || remote.protocol_version > our_protocol_version {
+        return Err(nack(format!(
+            "incompatible protocol version: remote={} ours={}",
+            remote.protocol_version, our_protocol_version
+        )));
+    }
+
+    if remote.feature_version == 0 || remote.feature_version > our_feature_version {
+        return Err(nack(format!(
+            "incompatible feature version: remote={} ours={}",
+            remote.feature_version, our_feature_version
+        )));
+    }
+
+    let required_capabilities = ["cognitive_processing"];
+    for required in required_capabilities {
+        if !remote.capabilities.iter().any(|c| c == required) {
+            return Err(nack(format!("missing required capability: {}", required)));
+        }
+    }
+
+    let agreed_protocol_version = our_protocol_version.min(remote.protocol_version);
+    let agreed_feature_version = our_feature_version.min(remote.feature_version);
+    let agreed_capabilities: Vec<String> = our_capabilities
+        .iter()
+        .filter(|c| remote.capabilities.contains(c))
+        .cloned()
+        .collect();
+
+    Ok(NegotiationAck {
+        status: "ack",
+        protocol_version: agreed_protocol_version,
+        feature_version: agreed_feature_version,
+        capabilities: agreed_capabilities,
+    })
+}
+
+fn nack(reason: String) -> NegotiationNack {
+    NegotiationNack {
+        status: "nack",
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote_json(protocol_version: u16, feature_version: u16, capabilities: &[&str]) -> String {
+        serde_json::to_string(&RemoteVersion {
+            protocol_version,
+            feature_version,
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn negotiate_acks_on_matching_versions_and_capabilities() {
+        let remote = remote_json(1, 1, &["cognitive_processing"]);
+        let ack = negotiate(1, 1, &["cognitive_processing".to_string()], &remote).unwrap();
+        assert_eq!(ack.protocol_version, 1);
+        assert_eq!(ack.feature_version, 1);
+    }
+
+    #[test]
+    fn negotiate_nacks_on_incompatible_protocol_version() {
+        let remote = remote_json(2, 1, &["cognitive_processing"]);
+        let result = negotiate(1, 1, &["cognitive_processing".to_string()], &remote);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn negotiate_nacks_on_incompatible_feature_version() {
+        // A remote advertising a feature version newer than ours must be
+        // rejected, not silently accepted (feature_version was previously
+        // dead state, never compared).
+        let remote = remote_json(1, 2, &["cognitive_processing"]);
+        let result = negotiate(1, 1, &["cognitive_processing".to_string()], &remote);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn negotiate_nacks_on_zero_feature_version() {
+        let remote = remote_json(1, 0, &["cognitive_processing"]);
+        let result = negotiate(1, 1, &["cognitive_processing".to_string()], &remote);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn negotiate_nacks_on_missing_required_capability() {
+        let remote = remote_json(1, 1, &["something_else"]);
+        let result = negotiate(1, 1, &["cognitive_processing".to_string()], &remote);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn negotiate_nacks_on_malformed_payload() {
+        let result = negotiate(1, 1, &["cognitive_processing".to_string()], "not json");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn negotiate_agrees_on_minimum_feature_version() {
+        let remote = remote_json(1, 1, &["cognitive_processing"]);
+        let ack = negotiate(1, 3, &["cognitive_processing".to_string()], &remote).unwrap();
+        assert_eq!(ack.feature_version, 1);
+    }
+}
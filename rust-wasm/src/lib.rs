@@ -1,7 +1,27 @@
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+mod safetensors;
+use safetensors::{LinearLayer, SafetensorsModel};
+
+mod attention;
+
+mod utility_ai;
+use utility_ai::{ModeUtility, UtilityScorer};
+
+mod telemetry;
+use telemetry::MetricsRegistry;
+
+mod handshake;
+
+mod tokenizer;
+use tokenizer::TextEncoder;
+
+mod rwkv;
+use rwkv::RwkvState;
+
 // Import the `console.log` function from the browser
 #[wasm_bindgen]
 extern "C" {
@@ -17,8 +37,11 @@ macro_rules! console_log {
 // Enhanced WASM module structure for HRM cognitive core with personality adaptation
 #[wasm_bindgen]
 pub struct HRMCognitive {
-    // HRM model weights (562M parameters)
+    // HRM model weights (562M parameters), zero-filled; only its length is
+    // reported as the parameter count while no safetensors checkpoint is loaded.
     weights: Vec<f32>,
+    // Parsed safetensors checkpoint backing the L/H-module linear layers, once loaded.
+    checkpoint: Option<SafetensorsModel>,
     // L-modules for sensory-motor patterns
     l_modules: Vec<LModule>,
     // H-modules for long-horizon planning
@@ -29,6 +52,15 @@ pub struct HRMCognitive {
     host_interface: HostInterface,
     // Cognitive state management
     cognitive_state: CognitiveState,
+    // Utility-AI scorer driving automatic ProcessingMode selection
+    utility_scorer: UtilityScorer,
+    // Per-mode utility breakdown from the most recent selection, for introspection
+    last_utility_breakdown: Vec<ModeUtility>,
+    // Scrape-able counters/gauges/histograms tracking runtime behavior
+    metrics: MetricsRegistry,
+    // HuggingFace tokenizer used to turn `context`/`task_type` into ids and,
+    // via the checkpoint's embedding table, a real sensory vector
+    text_encoder: Option<TextEncoder>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -36,6 +68,19 @@ pub struct LModule {
     pub id: u32,
     pub weights: Vec<f32>,
     pub activation: f32,
+    // Real linear layer sliced from the loaded checkpoint; absent until weights are loaded.
+    #[serde(skip)]
+    pub linear: Option<LinearLayer>,
+    // RWKV-style time-mixing recurrent state, carried across successive
+    // `process_cognitive_input` calls so the module retains temporal context.
+    #[serde(skip, default = "LModule::default_rwkv_state")]
+    pub rwkv_state: RwkvState,
+}
+
+impl LModule {
+    fn default_rwkv_state() -> RwkvState {
+        RwkvState::new(0)
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -44,6 +89,16 @@ pub struct HModule {
     pub weights: Vec<f32>,
     pub planning_depth: u32,
     pub activation: f32,
+    #[serde(skip)]
+    pub linear: Option<LinearLayer>,
+    // Number of ALiBi attention heads used when aggregating `context_window`.
+    #[serde(skip)]
+    pub num_heads: u32,
+    // Rolling window of past L-module activation vectors this H-module has
+    // attended over, bounded to `planning_depth + 1` entries (the max ALiBi
+    // distance plus the current step).
+    #[serde(skip)]
+    pub context_window: Vec<Vec<f32>>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -62,6 +117,8 @@ pub struct CognitiveOutput {
     pub h_module_activations: Vec<f32>,
     pub personality_influence: f32,
     pub adaptation_score: f32,
+    pub selected_processing_mode: String,
+    pub mode_utility_score: f32,
 }
 
 // Personality Adapter for user-specific behavior adaptation
@@ -89,12 +146,15 @@ pub struct HostInterface {
     pub connection_status: ConnectionStatus,
     pub message_queue: Vec<HostMessage>,
     pub capabilities: Vec<String>,
+    pub protocol_version: u16,
+    pub feature_version: u16,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub enum ConnectionStatus {
     Disconnected,
     Connecting,
+    Negotiating,
     Connected,
     Error(String),
 }
@@ -151,6 +211,7 @@ impl HRMCognitive {
 
         HRMCognitive {
             weights: vec![0.0; 562_741_762], // Actual HRM parameter count
+            checkpoint: None,
             l_modules: Vec::new(),
             h_modules: Vec::new(),
             personality_adapter: PersonalityAdapter {
@@ -169,6 +230,8 @@ impl HRMCognitive {
                     "memory_management".to_string(),
                     "emotional_modeling".to_string(),
                 ],
+                protocol_version: handshake::PROTOCOL_VERSION,
+                feature_version: handshake::FEATURE_VERSION,
             },
             cognitive_state: CognitiveState {
                 current_task: None,
@@ -182,6 +245,10 @@ impl HRMCognitive {
                 },
                 processing_mode: ProcessingMode::Analytical,
             },
+            utility_scorer: UtilityScorer::default(),
+            last_utility_breakdown: Vec::new(),
+            metrics: MetricsRegistry::default(),
+            text_encoder: None,
         }
     }
 
@@ -195,9 +262,11 @@ impl HRMCognitive {
                 id: i,
                 weights: vec![0.0; 1000], // Placeholder weights
                 activation: 0.0,
+                linear: None,
+                rwkv_state: RwkvState::new(0),
             });
         }
-        
+
         // Initialize H-modules
         for i in 0..h_count {
             self.h_modules.push(HModule {
@@ -205,8 +274,35 @@ impl HRMCognitive {
                 weights: vec![0.0; 2000], // Placeholder weights
                 planning_depth: 5,
                 activation: 0.0,
+                linear: None,
+                num_heads: 4,
+                context_window: Vec::new(),
             });
         }
+
+        self.wire_checkpoint_tensors();
+    }
+
+    // Slices named linear layers ("l_modules.{id}" / "h_modules.{id}") out of the
+    // loaded checkpoint and attaches them to the already-initialized modules.
+    // Modules whose tensors aren't present in the checkpoint keep `linear: None`
+    // and fall back to the averaged activation in `process_cognitive_input`.
+    fn wire_checkpoint_tensors(&mut self) {
+        let Some(model) = &self.checkpoint else { return };
+
+        for l_module in &mut self.l_modules {
+            let prefix = format!("l_modules.{}", l_module.id);
+            if let Ok(layer) = LinearLayer::from_checkpoint(model, &prefix) {
+                l_module.linear = Some(layer);
+            }
+        }
+
+        for h_module in &mut self.h_modules {
+            let prefix = format!("h_modules.{}", h_module.id);
+            if let Ok(layer) = LinearLayer::from_checkpoint(model, &prefix) {
+                h_module.linear = Some(layer);
+            }
+        }
     }
 
     #[wasm_bindgen]
@@ -223,30 +319,103 @@ impl HRMCognitive {
         };
 
         let processing_start = js_sys::Date::now();
+        self.metrics.incr_counter("hrm_process_cognitive_input_total", processing_start);
 
         // Update cognitive state
         self.cognitive_state.current_task = Some(input.task_type.clone());
-        self.update_attention_focus(&input.sensory_data);
+
+        // If the caller didn't pre-compute a sensory vector, derive one from
+        // `context`/`task_type` via the loaded tokenizer + checkpoint embedding.
+        let effective_sensory_data = if input.sensory_data.is_empty() {
+            self.encode_context_to_sensory(&input).unwrap_or_default()
+        } else {
+            input.sensory_data.clone()
+        };
+        self.update_attention_focus(&effective_sensory_data);
 
         // Apply personality adaptation
         let personality_influence = self.apply_personality_adaptation(&input);
 
-        // Process through L-modules with personality influence
+        // Refresh the utility scorer's creativity consideration from the
+        // actual personality metric before selecting a mode.
+        let creativity = *self.personality_adapter.personality_metrics.get("creativity").unwrap_or(&0.0);
+        self.utility_scorer.set_personality_creativity(creativity);
+
+        // Pick the highest-utility ProcessingMode for this input via the
+        // registered considerations, instead of relying on a manual setter.
+        let (selected_mode, mode_score, breakdown) =
+            self.utility_scorer.select_mode(&self.cognitive_state, &input);
+        self.cognitive_state.processing_mode = selected_mode.clone();
+        self.last_utility_breakdown = breakdown;
+
+        // Process through L-modules with personality influence. Each module
+        // first runs the sensory vector through its RWKV time-mixing state so
+        // activations carry temporal context from prior calls, rather than
+        // recomputing from scratch every time.
+        let personality_scale = 1.0 + personality_influence * 0.2;
         let mut l_activations = Vec::new();
         for (i, l_module) in self.l_modules.iter_mut().enumerate() {
-            let base_activation = (input.sensory_data.iter().sum::<f32>() / input.sensory_data.len() as f32)
-                * (i as f32 + 1.0) / 10.0;
-            l_module.activation = base_activation * (1.0 + personality_influence * 0.2);
+            l_module.rwkv_state.ensure_channels(effective_sensory_data.len());
+            let time_mixed = l_module.rwkv_state.step(&effective_sensory_data);
+
+            l_module.activation = match &l_module.linear {
+                // Real forward pass: project the time-mixed vector through the
+                // checkpoint-backed linear layer instead of averaging it.
+                Some(layer) => match layer.forward(&time_mixed) {
+                    Ok(out) => out.first().copied().unwrap_or(0.0) * personality_scale,
+                    Err(error) => {
+                        console_log!("L-module {} linear forward failed, using 0.0: {}", i, error);
+                        0.0
+                    }
+                },
+                None if !time_mixed.is_empty() => {
+                    (time_mixed.iter().sum::<f32>() / time_mixed.len() as f32)
+                        * (i as f32 + 1.0) / 10.0
+                        * personality_scale
+                }
+                None => 0.0,
+            };
             l_activations.push(l_module.activation);
         }
 
-        // Process through H-modules with emotional state influence
-        let mut h_activations = Vec::new();
+        // Process through H-modules with emotional state influence. Planning
+        // context is aggregated with ALiBi self-attention over each module's
+        // buffered activation window rather than a plain scalar sum.
         let emotional_modifier = self.cognitive_state.emotional_state.valence * 0.1 + 1.0;
+        let mut h_activations = Vec::new();
         for (i, h_module) in self.h_modules.iter_mut().enumerate() {
-            let base_activation = l_activations.iter().sum::<f32>() / (h_module.planning_depth as f32)
-                * (i as f32 + 1.0) / 5.0;
-            h_module.activation = base_activation * emotional_modifier;
+            // Buffer this step's L-activations and cap the window at the
+            // module's planning depth (the max ALiBi distance considered).
+            h_module.context_window.push(l_activations.clone());
+            let max_window = h_module.planning_depth as usize + 1;
+            if h_module.context_window.len() > max_window {
+                let excess = h_module.context_window.len() - max_window;
+                h_module.context_window.drain(0..excess);
+            }
+
+            let attended_context = attention::multi_head_alibi(
+                &h_module.context_window,
+                &h_module.context_window,
+                &l_activations,
+                h_module.num_heads as usize,
+                h_module.planning_depth,
+            );
+
+            h_module.activation = match &h_module.linear {
+                Some(layer) => match layer.forward(&attended_context) {
+                    Ok(out) => out.first().copied().unwrap_or(0.0) * emotional_modifier,
+                    Err(error) => {
+                        console_log!("H-module {} linear forward failed, using 0.0: {}", i, error);
+                        0.0
+                    }
+                },
+                None if !attended_context.is_empty() => {
+                    attended_context.iter().sum::<f32>() / (h_module.planning_depth as f32)
+                        * (i as f32 + 1.0) / 5.0
+                        * emotional_modifier
+                }
+                None => 0.0,
+            };
             h_activations.push(h_module.activation);
         }
 
@@ -260,6 +429,34 @@ impl HRMCognitive {
         let confidence = self.calculate_confidence(&l_activations, &h_activations);
         let adaptation_score = self.calculate_adaptation_score();
 
+        let now = js_sys::Date::now();
+        self.metrics
+            .observe_histogram("hrm_processing_time_ms", processing_time, now);
+        self.metrics.set_gauge(
+            "hrm_emotional_valence",
+            self.cognitive_state.emotional_state.valence as f64,
+            now,
+        );
+        self.metrics.set_gauge(
+            "hrm_emotional_arousal",
+            self.cognitive_state.emotional_state.arousal as f64,
+            now,
+        );
+        self.metrics.set_gauge(
+            "hrm_emotional_dominance",
+            self.cognitive_state.emotional_state.dominance as f64,
+            now,
+        );
+        self.metrics.set_gauge(
+            "hrm_memory_buffer_occupancy",
+            self.cognitive_state.memory_buffer.len() as f64,
+            now,
+        );
+        for (metric, value) in &self.personality_adapter.personality_metrics {
+            self.metrics
+                .set_gauge(format!("hrm_personality_{}", metric), *value as f64, now);
+        }
+
         // Generate enhanced output
         let output = CognitiveOutput {
             reasoning_result: self.generate_reasoning_result(&input, &l_activations, &h_activations),
@@ -269,6 +466,8 @@ impl HRMCognitive {
             h_module_activations: h_activations,
             personality_influence,
             adaptation_score,
+            selected_processing_mode: utility_ai::mode_name(&selected_mode).to_string(),
+            mode_utility_score: mode_score,
         };
 
         serde_json::to_string(&output).unwrap_or_else(|_| "{}".to_string())
@@ -276,73 +475,112 @@ impl HRMCognitive {
 
     #[wasm_bindgen]
     pub fn get_model_info(&self) -> String {
-        let info = format!(
+        let total_parameters = self
+            .checkpoint
+            .as_ref()
+            .map(|m| m.total_parameters)
+            .unwrap_or_else(|| self.weights.len());
+        format!(
             "{{\"total_parameters\": {}, \"l_modules\": {}, \"h_modules\": {}}}",
-            self.weights.len(),
+            total_parameters,
             self.l_modules.len(),
             self.h_modules.len()
-        );
-        info
+        )
+    }
+
+    /// Parse and load a real safetensors checkpoint, validating its layout
+    /// against the 562M-parameter HRM shape and wiring linear layers into the
+    /// already-initialized L/H-modules. Returns a structured JSON result
+    /// instead of a bare boolean so callers can surface the failure reason.
+    #[wasm_bindgen]
+    pub fn load_safetensors(&mut self, bytes: &[u8]) -> String {
+        console_log!("Parsing safetensors checkpoint... {} bytes", bytes.len());
+
+        match safetensors::load_safetensors(bytes) {
+            Ok(model) => {
+                let total_parameters = model.total_parameters;
+                let tensor_count = model.tensors.len();
+                self.checkpoint = Some(model);
+                self.wire_checkpoint_tensors();
+                console_log!("Loaded safetensors checkpoint: {} tensors, {} parameters", tensor_count, total_parameters);
+                format!(
+                    "{{\"success\": true, \"total_parameters\": {}, \"tensor_count\": {}}}",
+                    total_parameters, tensor_count
+                )
+            }
+            Err(error) => {
+                console_log!("Failed to load safetensors checkpoint: {}", error);
+                format!(
+                    "{{\"success\": false, \"error\": {}}}",
+                    serde_json::to_string(&error).unwrap_or_else(|_| "\"unknown error\"".to_string())
+                )
+            }
+        }
     }
 
     #[wasm_bindgen]
     pub fn load_weights(&mut self, weights_data: &[u8]) -> bool {
         console_log!("Loading HRM model weights into WASM... {} bytes", weights_data.len());
 
-        // Load weights directly into the WASM module's memory
-        if weights_data.len() >= 1024 {
-            // In a real implementation, this would parse safetensors format
-            // and load the 562M parameters into the weights vector
-
-            // For now, simulate loading by updating the weights vector size
-            if weights_data.len() > self.weights.len() * 4 { // 4 bytes per f32
-                console_log!("Expanding weights vector to accommodate model");
-                self.weights.resize(weights_data.len() / 4, 0.0);
+        match safetensors::load_safetensors(weights_data) {
+            Ok(model) => {
+                console_log!("Loaded {} parameters from safetensors checkpoint", model.total_parameters);
+                self.checkpoint = Some(model);
+                self.wire_checkpoint_tensors();
+                true
             }
-
-            // Simulate weight loading from bytes
-            for (i, chunk) in weights_data.chunks(4).enumerate() {
-                if i < self.weights.len() && chunk.len() == 4 {
-                    // Convert bytes to f32 (little-endian)
-                    let bytes = [chunk[0], chunk[1], chunk[2], chunk[3]];
-                    self.weights[i] = f32::from_le_bytes(bytes);
-                }
+            Err(error) => {
+                console_log!("Invalid HRM weights data: {}", error);
+                false
             }
-
-            console_log!("HRM weights loaded successfully into WASM module");
-            console_log!("Loaded {} parameters", self.weights.len());
-            true
-        } else {
-            console_log!("Invalid HRM weights data - too small");
-            false
         }
     }
 
+    /// Fetch HRM weights from `url` and resolve with the raw bytes as a
+    /// `Uint8Array`. This intentionally does not take `&mut self`: the fetch
+    /// is `await`ed, and holding a `&mut self` (or an aliased raw pointer to
+    /// it) across an await would let JS call another `&mut self` method on
+    /// the same instance while this future is still pending, producing
+    /// aliasing `&mut` references. Callers should pass the resolved bytes to
+    /// the synchronous `load_safetensors`/`load_weights` instead.
     #[wasm_bindgen]
-    pub fn load_weights_from_url(&mut self, url: &str) -> js_sys::Promise {
-        console_log!("Loading HRM weights from URL: {}", url);
-
-        // Return a promise that will load weights asynchronously
-        let promise = js_sys::Promise::new(&mut |resolve, _reject| {
-            // In a real implementation, this would fetch the weights from the URL
-            // For now, we'll simulate successful loading
-
-            let success_value = wasm_bindgen::JsValue::from(true);
-            resolve.call1(&wasm_bindgen::JsValue::NULL, &success_value).unwrap();
-        });
+    pub fn load_weights_from_url(url: &str) -> js_sys::Promise {
+        console_log!("Fetching HRM weights from URL: {}", url);
+        let url = url.to_string();
+
+        wasm_bindgen_futures::future_to_promise(async move {
+            let window = web_sys::window().ok_or_else(|| JsValue::from_str("no global window available"))?;
+            let response_value = wasm_bindgen_futures::JsFuture::from(window.fetch_with_str(&url)).await?;
+            let response: web_sys::Response = response_value.dyn_into()?;
+
+            if !response.ok() {
+                return Err(JsValue::from_str(&format!(
+                    "failed to fetch HRM weights from {}: HTTP {}",
+                    url,
+                    response.status()
+                )));
+            }
 
-        promise
+            let array_buffer = wasm_bindgen_futures::JsFuture::from(response.array_buffer()?).await?;
+            Ok(JsValue::from(js_sys::Uint8Array::new(&array_buffer)))
+        })
     }
 
     #[wasm_bindgen]
     pub fn get_weights_info(&self) -> String {
-        let info = format!(
-            "{{\"total_parameters\": {}, \"memory_usage_mb\": {:.2}, \"loaded\": {}}}",
-            self.weights.len(),
-            (self.weights.len() * 4) as f64 / (1024.0 * 1024.0),
-            !self.weights.is_empty()
-        );
-        info
+        match &self.checkpoint {
+            Some(model) => format!(
+                "{{\"total_parameters\": {}, \"memory_usage_mb\": {:.2}, \"loaded\": true}}",
+                model.total_parameters,
+                (model.total_parameters * 4) as f64 / (1024.0 * 1024.0)
+            ),
+            None => format!(
+                "{{\"total_parameters\": {}, \"memory_usage_mb\": {:.2}, \"loaded\": {}}}",
+                self.weights.len(),
+                (self.weights.len() * 4) as f64 / (1024.0 * 1024.0),
+                false
+            ),
+        }
     }
 
     // Personality adaptation methods
@@ -425,7 +663,7 @@ impl HRMCognitive {
         let h_variance = self.calculate_variance(h_activations);
         let emotional_stability = self.cognitive_state.emotional_state.stability;
 
-        ((l_variance + h_variance) / 2.0 * emotional_stability).min(1.0).max(0.0)
+        ((l_variance + h_variance) / 2.0 * emotional_stability).clamp(0.0, 1.0)
     }
 
     fn calculate_variance(&self, values: &[f32]) -> f32 {
@@ -450,7 +688,7 @@ impl HRMCognitive {
             .map(|event| event.user_feedback)
             .sum::<f32>();
 
-        (recent_events / 10.0).min(1.0).max(-1.0)
+        (recent_events / 10.0).clamp(-1.0, 1.0)
     }
 
     fn generate_reasoning_result(&self, input: &CognitiveInput, l_activations: &[f32], h_activations: &[f32]) -> String {
@@ -458,6 +696,10 @@ impl HRMCognitive {
         let h_avg = h_activations.iter().sum::<f32>() / h_activations.len() as f32;
         let emotional_influence = self.cognitive_state.emotional_state.valence;
 
+        // H-module activations are floats derived from attention/linear
+        // projections, not a generated token id sequence, so there is nothing
+        // genuine for `text_encoder` to decode yet. Keep the formatted
+        // description until the HRM actually produces output token ids.
         format!(
             "HRM processed '{}' with {:.1}% sensory activation, {:.1}% planning depth, emotional valence: {:.2}",
             input.task_type,
@@ -467,27 +709,63 @@ impl HRMCognitive {
         )
     }
 
+    /// Tokenize `task_type`/`context` and mean-pool their checkpoint
+    /// embeddings into a sensory vector, when both a tokenizer and a loaded
+    /// checkpoint are available.
+    fn encode_context_to_sensory(&self, input: &CognitiveInput) -> Option<Vec<f32>> {
+        let encoder = self.text_encoder.as_ref()?;
+        let model = self.checkpoint.as_ref()?;
+        let text = format!("{} {}", input.task_type, input.context);
+        let ids = encoder.encode(&text).ok()?;
+        tokenizer::embed_ids(model, &ids).ok()
+    }
+
     // Host interface methods
     #[wasm_bindgen]
     pub fn connect_to_desktop(&mut self, desktop_id: &str) -> bool {
         console_log!("Connecting to desktop: {}", desktop_id);
 
         self.host_interface.desktop_id = Some(desktop_id.to_string());
-        self.host_interface.connection_status = ConnectionStatus::Connected;
-
-        // Send initial capabilities message
-        let capabilities_msg = HostMessage {
-            id: format!("cap_{}", js_sys::Date::now() as u64),
-            message_type: "capabilities".to_string(),
-            payload: serde_json::to_string(&self.host_interface.capabilities).unwrap_or_default(),
-            timestamp: js_sys::Date::now(),
-            priority: 1,
-        };
-
-        self.host_interface.message_queue.push(capabilities_msg);
+        self.host_interface.connection_status = ConnectionStatus::Negotiating;
         true
     }
 
+    /// Complete the capability-negotiation handshake started by
+    /// `connect_to_desktop`. `remote_version_json` is the desktop's advertised
+    /// `{protocol_version, feature_version, capabilities}`. Only transitions
+    /// to `Connected` on a successful ACK; returns the ACK/NACK as JSON.
+    #[wasm_bindgen]
+    pub fn negotiate(&mut self, remote_version_json: &str) -> String {
+        console_log!("Negotiating capabilities with desktop...");
+
+        match handshake::negotiate(
+            self.host_interface.protocol_version,
+            self.host_interface.feature_version,
+            &self.host_interface.capabilities,
+            remote_version_json,
+        ) {
+            Ok(ack) => {
+                self.host_interface.connection_status = ConnectionStatus::Connected;
+
+                let capabilities_msg = HostMessage {
+                    id: format!("cap_{}", js_sys::Date::now() as u64),
+                    message_type: "capabilities".to_string(),
+                    payload: serde_json::to_string(&ack.capabilities).unwrap_or_default(),
+                    timestamp: js_sys::Date::now(),
+                    priority: 1,
+                };
+                self.host_interface.message_queue.push(capabilities_msg);
+
+                serde_json::to_string(&ack).unwrap_or_else(|_| "{\"status\": \"nack\"}".to_string())
+            }
+            Err(nack) => {
+                console_log!("Handshake failed: {}", nack.reason);
+                self.host_interface.connection_status = ConnectionStatus::Error(nack.reason.clone());
+                serde_json::to_string(&nack).unwrap_or_else(|_| "{\"status\": \"nack\"}".to_string())
+            }
+        }
+    }
+
     #[wasm_bindgen]
     pub fn send_host_message(&mut self, message_type: &str, payload: &str) -> String {
         let message = HostMessage {
@@ -586,6 +864,89 @@ impl HRMCognitive {
         console_log!("Processing mode set to: {}", mode);
     }
 
+    /// Register a custom utility-AI consideration. `callback` is invoked with
+    /// `(state_json, input_json)` and must return a score in [0, 1].
+    /// `mode_weights_json` is a JSON object mapping mode name ("analytical",
+    /// "creative", "reactive", "contemplative") to a weight in [0, 1] giving
+    /// this consideration real per-mode influence over `select_mode`; modes
+    /// omitted from the object (or the whole argument, if empty/invalid) fall
+    /// back to the built-in default weight.
+    #[wasm_bindgen]
+    pub fn register_consideration(&mut self, name: &str, callback: js_sys::Function, mode_weights_json: &str) {
+        let consideration = Box::new(utility_ai::JsConsideration::new(name.to_string(), callback));
+        match serde_json::from_str::<HashMap<String, f32>>(mode_weights_json) {
+            Ok(weights) if !weights.is_empty() => self.utility_scorer.register_with_weights(consideration, weights),
+            _ => self.utility_scorer.register(consideration),
+        }
+    }
+
+    /// Per-mode utility scores from the most recent `process_cognitive_input`
+    /// call, so hosts can inspect why a given ProcessingMode was picked.
+    #[wasm_bindgen]
+    pub fn get_utility_breakdown(&self) -> String {
+        serde_json::to_string(&self.last_utility_breakdown).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// Load a HuggingFace `tokenizer.json` so `context`/`task_type` text can
+    /// be tokenized and embedded into real sensory vectors.
+    #[wasm_bindgen]
+    pub fn load_tokenizer(&mut self, json: &str) -> bool {
+        match TextEncoder::from_json(json) {
+            Ok(encoder) => {
+                self.text_encoder = Some(encoder);
+                true
+            }
+            Err(error) => {
+                console_log!("Failed to load tokenizer: {}", error);
+                false
+            }
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn encode(&self, text: &str) -> Vec<u32> {
+        self.text_encoder
+            .as_ref()
+            .and_then(|encoder| encoder.encode(text).ok())
+            .unwrap_or_default()
+    }
+
+    #[wasm_bindgen]
+    pub fn decode(&self, ids: Vec<u32>) -> String {
+        self.text_encoder
+            .as_ref()
+            .and_then(|encoder| encoder.decode(&ids).ok())
+            .unwrap_or_default()
+    }
+
+    /// Vocabulary size of the loaded tokenizer, or 0 if none is loaded.
+    #[wasm_bindgen]
+    pub fn get_vocab_size(&self) -> usize {
+        self.text_encoder.as_ref().map(|encoder| encoder.vocab_size()).unwrap_or(0)
+    }
+
+    /// Render the metrics registry in Prometheus text exposition format.
+    #[wasm_bindgen]
+    pub fn export_metrics_prometheus(&self) -> String {
+        self.metrics.export_prometheus()
+    }
+
+    /// Drain and return metric samples that changed since the last call, as
+    /// a JSON array, so a host can stream realtime telemetry over a WebSocket.
+    #[wasm_bindgen]
+    pub fn drain_metric_events(&mut self) -> String {
+        serde_json::to_string(&self.metrics.drain_events()).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// Clear every L-module's RWKV recurrent state, e.g. between sessions,
+    /// without touching loaded weights, memory, or personality adaptation.
+    #[wasm_bindgen]
+    pub fn reset_recurrent_state(&mut self) {
+        for l_module in &mut self.l_modules {
+            l_module.rwkv_state.reset();
+        }
+    }
+
     #[wasm_bindgen]
     pub fn clear_memory_buffer(&mut self) {
         self.cognitive_state.memory_buffer.clear();
@@ -615,6 +976,12 @@ impl HRMCognitive {
     }
 }
 
+impl Default for HRMCognitive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // Initialize the enhanced WASM module
 #[wasm_bindgen(start)]
 pub fn main() {
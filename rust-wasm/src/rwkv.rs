@@ -0,0 +1,82 @@
+// RWKV-style linear-attention time-mixing state for the L-modules, giving
+// each module constant-memory O(1)-per-step recurrence across successive
+// `process_cognitive_input` calls instead of recomputing from scratch.
+
+/// Per-channel WKV recurrent state: running weighted-sum numerator/denominator
+/// `(a, b)` plus the previous raw input, carried between steps.
+#[derive(Clone)]
+pub struct RwkvState {
+    a: Vec<f32>,
+    b: Vec<f32>,
+    prev: Vec<f32>,
+    // Time-decay (w) and bonus (u) parameters, one per channel.
+    decay: Vec<f32>,
+    bonus: Vec<f32>,
+    // Mix factor between the current and previous input, per channel. Like
+    // decay and bonus it is fixed at its initial value; nothing loads it from
+    // the checkpoint.
+    mix: Vec<f32>,
+}
+
+impl RwkvState {
+    pub fn new(channels: usize) -> Self {
+        RwkvState {
+            a: vec![0.0; channels],
+            b: vec![0.0; channels],
+            prev: vec![0.0; channels],
+            decay: vec![0.5; channels],
+            bonus: vec![0.0; channels],
+            mix: vec![0.5; channels],
+        }
+    }
+
+    /// Resize the state to match a new channel count (the sensory vector
+    /// length can vary between calls), zeroing the recurrent accumulators.
+    pub fn ensure_channels(&mut self, channels: usize) {
+        if channels == self.a.len() {
+            return;
+        }
+        self.a = vec![0.0; channels];
+        self.b = vec![0.0; channels];
+        self.prev = vec![0.0; channels];
+        self.decay = vec![0.5; channels];
+        self.bonus = vec![0.0; channels];
+        self.mix = vec![0.5; channels];
+    }
+
+    pub fn reset(&mut self) {
+        self.a.iter_mut().for_each(|v| *v = 0.0);
+        self.b.iter_mut().for_each(|v| *v = 0.0);
+        self.prev.iter_mut().for_each(|v| *v = 0.0);
+    }
+
+    /// Time-mix `input` against the carried `prev` value, run the WKV
+    /// recurrence, and update state in place. Returns the per-channel `wkv`
+    /// output (the value fed forward as this step's activation).
+    pub fn step(&mut self, input: &[f32]) -> Vec<f32> {
+        let channels = self.a.len();
+        let mut wkv = vec![0.0; channels];
+
+        for (c, wkv_c) in wkv.iter_mut().enumerate() {
+            let x = *input.get(c).unwrap_or(&0.0);
+            let mu = self.mix[c];
+            // Time-mixed input: interpolate current and previous channel values.
+            let k = mu * x + (1.0 - mu) * self.prev[c];
+            let v = k;
+
+            let w = self.decay[c];
+            let u = self.bonus[c];
+
+            let numerator = self.a[c] + (u + k).exp() * v;
+            let denominator = self.b[c] + (u + k).exp();
+            *wkv_c = numerator / denominator.max(f32::EPSILON);
+
+            let decay_factor = (-w.exp()).exp();
+            self.a[c] = decay_factor * self.a[c] + k.exp() * v;
+            self.b[c] = decay_factor * self.b[c] + k.exp();
+            self.prev[c] = x;
+        }
+
+        wkv
+    }
+}
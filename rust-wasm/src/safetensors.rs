@@ -0,0 +1,268 @@
+// Minimal safetensors reader + candle tensor construction for the HRM checkpoint.
+//
+// Format: 8-byte little-endian header length, followed by a JSON header
+// `{ tensor_name: { "dtype": ..., "shape": [...], "data_offsets": [start, end] }, ... }`
+// (an optional "__metadata__" entry is ignored), followed by the raw tensor bytes.
+
+use candle_core::{DType, Device, Tensor};
+use std::collections::HashMap;
+
+/// Expected total parameter count for the 562M-parameter HRM layout.
+pub const EXPECTED_PARAMETER_COUNT: usize = 562_741_762;
+
+#[derive(Debug, Clone, serde::Deserialize)]
+struct TensorMeta {
+    dtype: String,
+    shape: Vec<usize>,
+    data_offsets: [usize; 2],
+}
+
+/// A single named tensor loaded from a safetensors checkpoint.
+#[derive(Debug, Clone)]
+pub struct NamedTensor {
+    pub name: String,
+    pub shape: Vec<usize>,
+    pub tensor: Tensor,
+}
+
+/// A fully parsed safetensors checkpoint, indexed by tensor name.
+#[derive(Debug, Default)]
+pub struct SafetensorsModel {
+    pub tensors: HashMap<String, NamedTensor>,
+    pub total_parameters: usize,
+}
+
+impl SafetensorsModel {
+    pub fn get(&self, name: &str) -> Result<&NamedTensor, String> {
+        self.tensors
+            .get(name)
+            .ok_or_else(|| format!("safetensors: missing tensor '{}'", name))
+    }
+}
+
+fn dtype_from_str(dtype: &str) -> Result<DType, String> {
+    match dtype {
+        "F32" => Ok(DType::F32),
+        "F16" => Ok(DType::F16),
+        "BF16" => Ok(DType::BF16),
+        other => Err(format!("safetensors: unsupported dtype '{}'", other)),
+    }
+}
+
+/// Parse a safetensors byte buffer into named candle tensors, validating that
+/// the checkpoint matches the expected 562M-parameter HRM layout.
+pub fn load_safetensors(bytes: &[u8]) -> Result<SafetensorsModel, String> {
+    if bytes.len() < 8 {
+        return Err("safetensors: buffer too small for header length".to_string());
+    }
+
+    let header_len = u64::from_le_bytes(bytes[0..8].try_into().unwrap()) as usize;
+    let header_start: usize = 8;
+    let header_end = header_start
+        .checked_add(header_len)
+        .ok_or_else(|| "safetensors: header length overflow".to_string())?;
+
+    if header_end > bytes.len() {
+        return Err("safetensors: header length exceeds buffer size".to_string());
+    }
+
+    let header_json = std::str::from_utf8(&bytes[header_start..header_end])
+        .map_err(|e| format!("safetensors: header is not valid UTF-8: {}", e))?;
+
+    let raw_header: HashMap<String, serde_json::Value> = serde_json::from_str(header_json)
+        .map_err(|e| format!("safetensors: invalid header JSON: {}", e))?;
+
+    let data_start = header_end;
+    let device = Device::Cpu;
+    let mut tensors = HashMap::new();
+    let mut total_parameters = 0usize;
+
+    for (name, value) in raw_header {
+        if name == "__metadata__" {
+            continue;
+        }
+
+        let meta: TensorMeta = serde_json::from_value(value)
+            .map_err(|e| format!("safetensors: malformed metadata for '{}': {}", name, e))?;
+
+        let dtype = dtype_from_str(&meta.dtype)?;
+        let [start, end] = meta.data_offsets;
+        let abs_start = data_start
+            .checked_add(start)
+            .ok_or_else(|| format!("safetensors: offset overflow for '{}'", name))?;
+        let abs_end = data_start
+            .checked_add(end)
+            .ok_or_else(|| format!("safetensors: offset overflow for '{}'", name))?;
+
+        if abs_end > bytes.len() || abs_start > abs_end {
+            return Err(format!(
+                "safetensors: data offsets for '{}' out of bounds ({}..{}, buffer len {})",
+                name,
+                abs_start,
+                abs_end,
+                bytes.len()
+            ));
+        }
+
+        let element_count: usize = meta.shape.iter().product();
+        total_parameters += element_count;
+
+        let slice = &bytes[abs_start..abs_end];
+        let tensor = Tensor::from_raw_buffer(slice, dtype, &meta.shape, &device)
+            .map_err(|e| format!("safetensors: failed to build tensor '{}': {}", name, e))?;
+
+        tensors.insert(
+            name.clone(),
+            NamedTensor {
+                name,
+                shape: meta.shape,
+                tensor,
+            },
+        );
+    }
+
+    if total_parameters != EXPECTED_PARAMETER_COUNT {
+        return Err(format!(
+            "safetensors: checkpoint has {} parameters, expected {} for the HRM layout",
+            total_parameters, EXPECTED_PARAMETER_COUNT
+        ));
+    }
+
+    Ok(SafetensorsModel {
+        tensors,
+        total_parameters,
+    })
+}
+
+/// A single linear layer (`y = xW^T + b`) sliced out of the loaded checkpoint.
+pub struct LinearLayer {
+    pub weight: Tensor,
+    pub bias: Option<Tensor>,
+}
+
+impl LinearLayer {
+    pub fn from_checkpoint(model: &SafetensorsModel, prefix: &str) -> Result<Self, String> {
+        let weight = model.get(&format!("{}.weight", prefix))?.tensor.clone();
+        let bias = model
+            .get(&format!("{}.bias", prefix))
+            .ok()
+            .map(|t| t.tensor.clone());
+        Ok(LinearLayer { weight, bias })
+    }
+
+    /// Run `x` (shape `[1, in_features]`) through the layer, returning a flat `Vec<f32>`.
+    ///
+    /// The input vector built from module activations is always `F32`, so the
+    /// checkpoint weight/bias (which may be `F16`/`BF16`) are cast up to `F32`
+    /// first instead of letting candle's dtype-mismatched `matmul` fail.
+    pub fn forward(&self, x: &[f32]) -> Result<Vec<f32>, String> {
+        let device = self.weight.device().clone();
+        let weight = cast_to_f32(&self.weight, "weight")?;
+
+        let (_out_features, in_features) = weight
+            .dims2()
+            .map_err(|e| format!("linear: expected a 2D weight tensor: {}", e))?;
+        if x.len() != in_features {
+            return Err(format!(
+                "linear: input has {} features, layer expects {}",
+                x.len(),
+                in_features
+            ));
+        }
+
+        let input = Tensor::from_vec(x.to_vec(), (1, in_features), &device)
+            .map_err(|e| format!("linear: failed to build input tensor: {}", e))?;
+
+        let weight_t = weight
+            .t()
+            .map_err(|e| format!("linear: failed to transpose weight: {}", e))?;
+        let mut out = input
+            .matmul(&weight_t)
+            .map_err(|e| format!("linear: matmul failed: {}", e))?;
+
+        if let Some(bias) = &self.bias {
+            let bias = cast_to_f32(bias, "bias")?;
+            out = out
+                .broadcast_add(&bias)
+                .map_err(|e| format!("linear: bias add failed: {}", e))?;
+        }
+
+        out.flatten_all()
+            .and_then(|t| t.to_vec1::<f32>())
+            .map_err(|e| format!("linear: failed to read output: {}", e))
+    }
+}
+
+pub(crate) fn cast_to_f32(tensor: &Tensor, label: &str) -> Result<Tensor, String> {
+    if tensor.dtype() == DType::F32 {
+        Ok(tensor.clone())
+    } else {
+        tensor
+            .to_dtype(DType::F32)
+            .map_err(|e| format!("linear: failed to cast {} to f32: {}", label, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_checkpoint(header_json: &str, data: &[u8]) -> Vec<u8> {
+        let mut bytes = (header_json.len() as u64).to_le_bytes().to_vec();
+        bytes.extend_from_slice(header_json.as_bytes());
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    #[test]
+    fn load_safetensors_rejects_buffer_too_small_for_header() {
+        let err = load_safetensors(&[0u8; 4]).unwrap_err();
+        assert!(err.contains("too small"));
+    }
+
+    #[test]
+    fn load_safetensors_rejects_header_length_exceeding_buffer() {
+        let mut bytes = 1000u64.to_le_bytes().to_vec();
+        bytes.extend_from_slice(b"{}");
+        let err = load_safetensors(&bytes).unwrap_err();
+        assert!(err.contains("exceeds buffer size"));
+    }
+
+    #[test]
+    fn load_safetensors_rejects_out_of_bounds_data_offsets() {
+        let header = r#"{"w":{"dtype":"F32","shape":[1],"data_offsets":[0,100]}}"#;
+        let bytes = build_checkpoint(header, &[0u8; 4]);
+        let err = load_safetensors(&bytes).unwrap_err();
+        assert!(err.contains("out of bounds"));
+    }
+
+    #[test]
+    fn load_safetensors_rejects_unexpected_parameter_count() {
+        let header = r#"{"w":{"dtype":"F32","shape":[1],"data_offsets":[0,4]}}"#;
+        let data = 1.0f32.to_le_bytes();
+        let bytes = build_checkpoint(header, &data);
+        let err = load_safetensors(&bytes).unwrap_err();
+        assert!(err.contains("parameters, expected"));
+    }
+
+    #[test]
+    fn linear_layer_forward_rejects_dimension_mismatch() {
+        let weight = Tensor::from_vec(vec![1.0f32, 2.0, 3.0, 4.0], (2, 2), &Device::Cpu).unwrap();
+        let layer = LinearLayer { weight, bias: None };
+
+        let err = layer.forward(&[1.0, 2.0, 3.0]).unwrap_err();
+        assert!(err.contains("expects 2"));
+    }
+
+    #[test]
+    fn linear_layer_forward_casts_f16_weights_before_matmul() {
+        let weight = Tensor::from_vec(vec![1.0f32, 0.0, 0.0, 1.0], (2, 2), &Device::Cpu)
+            .unwrap()
+            .to_dtype(DType::F16)
+            .unwrap();
+        let layer = LinearLayer { weight, bias: None };
+
+        let out = layer.forward(&[1.0, 2.0]).unwrap();
+        assert_eq!(out.len(), 2);
+    }
+}
@@ -0,0 +1,127 @@
+// Metrics registry exposing cognitive state as Prometheus-scrapeable
+// counters/gauges/histograms, plus a drain-style event queue for streaming
+// realtime telemetry to a host over a WebSocket.
+
+use std::collections::HashMap;
+
+const HISTOGRAM_BUCKETS_MS: [f64; 8] = [1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0];
+
+#[derive(Clone, serde::Serialize)]
+pub struct MetricSample {
+    pub name: String,
+    pub value: f64,
+    pub timestamp: f64,
+}
+
+struct Histogram {
+    bucket_counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Histogram {
+            bucket_counts: vec![0; HISTOGRAM_BUCKETS_MS.len() + 1], // + the +Inf bucket
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        self.sum += value;
+        self.count += 1;
+        for (i, bound) in HISTOGRAM_BUCKETS_MS.iter().enumerate() {
+            if value <= *bound {
+                self.bucket_counts[i] += 1;
+            }
+        }
+        *self.bucket_counts.last_mut().unwrap() += 1; // +Inf always counts
+    }
+}
+
+/// Registry of counters, gauges, and histograms tracking `HRMCognitive`'s
+/// runtime behavior, renderable as Prometheus text exposition format.
+#[derive(Default)]
+pub struct MetricsRegistry {
+    counters: HashMap<&'static str, u64>,
+    gauges: HashMap<String, f64>,
+    histograms: HashMap<&'static str, Histogram>,
+    pending_events: Vec<MetricSample>,
+}
+
+impl MetricsRegistry {
+    pub fn incr_counter(&mut self, name: &'static str, timestamp: f64) {
+        let value = self.counters.entry(name).or_insert(0);
+        *value += 1;
+        self.pending_events.push(MetricSample {
+            name: name.to_string(),
+            value: *value as f64,
+            timestamp,
+        });
+    }
+
+    pub fn set_gauge(&mut self, name: impl Into<String>, value: f64, timestamp: f64) {
+        let name = name.into();
+        self.gauges.insert(name.clone(), value);
+        self.pending_events.push(MetricSample {
+            name,
+            value,
+            timestamp,
+        });
+    }
+
+    pub fn observe_histogram(&mut self, name: &'static str, value_ms: f64, timestamp: f64) {
+        let histogram = self.histograms.entry(name).or_insert_with(Histogram::new);
+        histogram.observe(value_ms);
+        self.pending_events.push(MetricSample {
+            name: format!("{}_sum", name),
+            value: histogram.sum,
+            timestamp,
+        });
+    }
+
+    /// Drain and return newly-changed metric samples, for a host to stream
+    /// over a WebSocket without re-scraping the full registry.
+    pub fn drain_events(&mut self) -> Vec<MetricSample> {
+        std::mem::take(&mut self.pending_events)
+    }
+
+    /// Render the full registry in Prometheus text exposition format.
+    pub fn export_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        for (name, value) in &self.counters {
+            out.push_str(&format!("# HELP {name} Total count of {name}.\n"));
+            out.push_str(&format!("# TYPE {name} counter\n"));
+            out.push_str(&format!("{name} {value}\n"));
+        }
+
+        for (name, value) in &self.gauges {
+            let metric_name = sanitize_metric_name(name);
+            out.push_str(&format!("# HELP {metric_name} Current value of {metric_name}.\n"));
+            out.push_str(&format!("# TYPE {metric_name} gauge\n"));
+            out.push_str(&format!("{metric_name} {value}\n"));
+        }
+
+        for (name, histogram) in &self.histograms {
+            out.push_str(&format!("# HELP {name} Histogram of {name} in milliseconds.\n"));
+            out.push_str(&format!("# TYPE {name} histogram\n"));
+            for (bound, count) in HISTOGRAM_BUCKETS_MS.iter().zip(histogram.bucket_counts.iter()) {
+                out.push_str(&format!("{name}_bucket{{le=\"{bound}\"}} {count}\n"));
+            }
+            let inf_count = histogram.bucket_counts.last().copied().unwrap_or(0);
+            out.push_str(&format!("{name}_bucket{{le=\"+Inf\"}} {inf_count}\n"));
+            out.push_str(&format!("{name}_sum {}\n", histogram.sum));
+            out.push_str(&format!("{name}_count {}\n", histogram.count));
+        }
+
+        out
+    }
+}
+
+fn sanitize_metric_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
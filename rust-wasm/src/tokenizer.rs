@@ -0,0 +1,132 @@
+// HuggingFace tokenizer.json loading plus an embedding lookup sliced from the
+// same safetensors checkpoint, so `CognitiveInput.context`/`task_type` can
+// drive real sensory vectors instead of requiring pre-computed floats.
+
+use crate::safetensors::{cast_to_f32, SafetensorsModel};
+use std::str::FromStr;
+use tokenizers::Tokenizer;
+
+/// Name of the token-embedding tensor inside the HRM checkpoint.
+const EMBEDDING_TENSOR: &str = "embeddings.weight";
+
+pub struct TextEncoder {
+    tokenizer: Tokenizer,
+}
+
+impl TextEncoder {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let tokenizer =
+            Tokenizer::from_str(json).map_err(|e| format!("tokenizer: failed to parse tokenizer.json: {}", e))?;
+        Ok(TextEncoder { tokenizer })
+    }
+
+    pub fn encode(&self, text: &str) -> Result<Vec<u32>, String> {
+        self.tokenizer
+            .encode(text, true)
+            .map(|enc| enc.get_ids().to_vec())
+            .map_err(|e| format!("tokenizer: failed to encode text: {}", e))
+    }
+
+    pub fn decode(&self, ids: &[u32]) -> Result<String, String> {
+        self.tokenizer
+            .decode(ids, true)
+            .map_err(|e| format!("tokenizer: failed to decode ids: {}", e))
+    }
+
+    pub fn vocab_size(&self) -> usize {
+        self.tokenizer.get_vocab_size(true)
+    }
+}
+
+/// Look up `ids` in the checkpoint's embedding table and mean-pool the rows
+/// into a single sensory vector the L-modules can consume.
+pub fn embed_ids(model: &SafetensorsModel, ids: &[u32]) -> Result<Vec<f32>, String> {
+    let embedding = model.get(EMBEDDING_TENSOR)?;
+    let hidden_dim = *embedding
+        .shape
+        .get(1)
+        .ok_or_else(|| "tokenizer: embedding tensor has no hidden dimension".to_string())?;
+
+    if ids.is_empty() {
+        return Ok(vec![0.0; hidden_dim]);
+    }
+
+    // Checkpoints commonly store embeddings in F16/BF16; `to_vec2::<f32>`
+    // errors on anything but F32, so cast up first (mirrors safetensors.rs's
+    // `LinearLayer::forward`).
+    let embedding_tensor = cast_to_f32(&embedding.tensor, "embeddings.weight")?;
+    let rows = embedding_tensor
+        .to_vec2::<f32>()
+        .map_err(|e| format!("tokenizer: failed to read embedding table: {}", e))?;
+
+    let mut pooled = vec![0.0f32; hidden_dim];
+    for &id in ids {
+        let row = rows
+            .get(id as usize)
+            .ok_or_else(|| format!("tokenizer: token id {} out of embedding range", id))?;
+        for (p, v) in pooled.iter_mut().zip(row.iter()) {
+            *p += v;
+        }
+    }
+    for p in pooled.iter_mut() {
+        *p /= ids.len() as f32;
+    }
+
+    Ok(pooled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::safetensors::{NamedTensor, SafetensorsModel};
+    use candle_core::{DType, Device, Tensor};
+    use std::collections::HashMap;
+
+    fn model_with_embedding(tensor: Tensor, shape: Vec<usize>) -> SafetensorsModel {
+        let mut tensors = HashMap::new();
+        tensors.insert(
+            EMBEDDING_TENSOR.to_string(),
+            NamedTensor {
+                name: EMBEDDING_TENSOR.to_string(),
+                shape,
+                tensor,
+            },
+        );
+        SafetensorsModel {
+            tensors,
+            total_parameters: 0,
+        }
+    }
+
+    #[test]
+    fn embed_ids_casts_f16_embedding_table_before_reading() {
+        // Regression test: a realistic checkpoint stores embeddings in
+        // F16/BF16, and `to_vec2::<f32>` errors on anything but F32.
+        let tensor = Tensor::from_vec(vec![1.0f32, 2.0, 3.0, 4.0], (2, 2), &Device::Cpu)
+            .unwrap()
+            .to_dtype(DType::F16)
+            .unwrap();
+        let model = model_with_embedding(tensor, vec![2, 2]);
+
+        let pooled = embed_ids(&model, &[0]).unwrap();
+        assert_eq!(pooled, vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn embed_ids_rejects_out_of_range_token_id() {
+        let tensor = Tensor::from_vec(vec![1.0f32, 2.0], (1, 2), &Device::Cpu).unwrap();
+        let model = model_with_embedding(tensor, vec![1, 2]);
+
+        let err = embed_ids(&model, &[5]).unwrap_err();
+        assert!(err.contains("out of embedding range"));
+    }
+
+    #[test]
+    fn embed_ids_returns_zero_vector_for_empty_ids() {
+        let tensor = Tensor::from_vec(vec![1.0f32, 2.0], (1, 2), &Device::Cpu).unwrap();
+        let model = model_with_embedding(tensor, vec![1, 2]);
+
+        let pooled = embed_ids(&model, &[]).unwrap();
+        assert_eq!(pooled, vec![0.0, 0.0]);
+    }
+}
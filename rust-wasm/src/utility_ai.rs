@@ -0,0 +1,455 @@
+// Utility-AI scoring layer that drives `ProcessingMode` selection.
+//
+// Each `Consideration` scores a candidate mode against the current
+// `CognitiveState`/`CognitiveInput` in [0, 1], then `weighted_score` blends
+// that raw score against the mode's per-consideration weight (0.0 neutralizes
+// it instead of zeroing the mode outright). The blended scores are combined
+// with a compensation-corrected weighted product (the "Apex" utility-AI
+// formula): the raw product is corrected back up proportionally to the
+// number of considerations so a handful of middling scores don't get crushed
+// the way a plain product would, while a score still near zero after
+// weighting vetoes that mode.
+
+use crate::{CognitiveInput, CognitiveState, ProcessingMode};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+pub trait Consideration {
+    fn name(&self) -> &str;
+    fn score(&self, state: &CognitiveState, input: &CognitiveInput) -> f32;
+}
+
+pub struct ArousalConsideration;
+impl Consideration for ArousalConsideration {
+    fn name(&self) -> &str {
+        "arousal"
+    }
+    fn score(&self, state: &CognitiveState, _input: &CognitiveInput) -> f32 {
+        state.emotional_state.arousal.clamp(0.0, 1.0)
+    }
+}
+
+pub struct ConfidenceConsideration;
+impl Consideration for ConfidenceConsideration {
+    fn name(&self) -> &str {
+        "confidence"
+    }
+    fn score(&self, state: &CognitiveState, _input: &CognitiveInput) -> f32 {
+        (state.emotional_state.dominance * 0.5 + state.emotional_state.stability * 0.5).clamp(0.0, 1.0)
+    }
+}
+
+pub struct MemorySignificanceConsideration;
+impl Consideration for MemorySignificanceConsideration {
+    fn name(&self) -> &str {
+        "memory_significance"
+    }
+    fn score(&self, state: &CognitiveState, _input: &CognitiveInput) -> f32 {
+        if state.memory_buffer.is_empty() {
+            return 0.0;
+        }
+        let avg_importance = state.memory_buffer.iter().map(|m| m.importance).sum::<f32>()
+            / state.memory_buffer.len() as f32;
+        avg_importance.clamp(0.0, 1.0)
+    }
+}
+
+// Shared with `UtilityScorer::set_personality_creativity`, which refreshes it
+// from `personality_adapter.personality_metrics["creativity"]` every
+// `process_cognitive_input` call.
+pub struct PersonalityCreativityConsideration {
+    pub creativity: Rc<Cell<f32>>,
+}
+impl Consideration for PersonalityCreativityConsideration {
+    fn name(&self) -> &str {
+        "personality_creativity"
+    }
+    fn score(&self, _state: &CognitiveState, _input: &CognitiveInput) -> f32 {
+        ((self.creativity.get() + 1.0) / 2.0).clamp(0.0, 1.0)
+    }
+}
+
+/// Per-mode consideration weight, blended against a neutral baseline rather
+/// than multiplied straight through (see `weighted_score`): a weight of `1.0`
+/// lets a consideration's raw score matter fully for this mode, a weight of
+/// `0.0` neutralizes it to `1.0` so it can't veto a mode it isn't relevant
+/// to. This is what lets e.g. an empty memory buffer (`memory_significance ==
+/// 0.0`) pull Contemplative down hard while barely touching Reactive,
+/// instead of zeroing every mode identically.
+struct ModeAffinity {
+    mode: ProcessingMode,
+    weights: HashMap<&'static str, f32>,
+}
+
+/// Breakdown of how a single `ProcessingMode` scored, surfaced to JS via
+/// `get_utility_breakdown` so callers can see why a mode was (or wasn't) chosen.
+#[derive(serde::Serialize)]
+pub struct ModeUtility {
+    pub mode: String,
+    pub score: f32,
+    pub consideration_scores: HashMap<String, f32>,
+}
+
+/// A consideration backed by a JS scoring callback, registered at runtime via
+/// `register_consideration`. The callback receives the serialized cognitive
+/// state and input as JSON strings and must return a number in [0, 1].
+pub struct JsConsideration {
+    name: String,
+    callback: js_sys::Function,
+}
+
+impl JsConsideration {
+    pub fn new(name: String, callback: js_sys::Function) -> Self {
+        JsConsideration { name, callback }
+    }
+}
+
+impl Consideration for JsConsideration {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn score(&self, state: &CognitiveState, input: &CognitiveInput) -> f32 {
+        let state_json = serde_json::to_string(state).unwrap_or_default();
+        let input_json = serde_json::to_string(input).unwrap_or_default();
+        let state_arg = wasm_bindgen::JsValue::from_str(&state_json);
+        let input_arg = wasm_bindgen::JsValue::from_str(&input_json);
+
+        self.callback
+            .call2(&wasm_bindgen::JsValue::NULL, &state_arg, &input_arg)
+            .ok()
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32)
+            .unwrap_or(0.0)
+    }
+}
+
+/// Registry of pluggable considerations driving `ProcessingMode` selection.
+pub struct UtilityScorer {
+    considerations: Vec<Box<dyn Consideration>>,
+    personality_creativity: Rc<Cell<f32>>,
+    // Per-mode weights for considerations registered via `register_with_weights`
+    // (e.g. `JsConsideration`), keyed by consideration name then mode name.
+    // Considerations with no entry here fall back to the built-in default
+    // weight in `select_mode`.
+    custom_weights: HashMap<String, HashMap<String, f32>>,
+}
+
+impl Default for UtilityScorer {
+    fn default() -> Self {
+        let personality_creativity = Rc::new(Cell::new(0.0));
+        let mut scorer = UtilityScorer {
+            considerations: Vec::new(),
+            personality_creativity: personality_creativity.clone(),
+            custom_weights: HashMap::new(),
+        };
+        scorer.register(Box::new(ArousalConsideration));
+        scorer.register(Box::new(ConfidenceConsideration));
+        scorer.register(Box::new(MemorySignificanceConsideration));
+        scorer.register(Box::new(PersonalityCreativityConsideration {
+            creativity: personality_creativity,
+        }));
+        scorer
+    }
+}
+
+impl UtilityScorer {
+    pub fn register(&mut self, consideration: Box<dyn Consideration>) {
+        self.considerations.push(consideration);
+    }
+
+    /// Register a consideration with explicit per-mode weights (mode name ->
+    /// weight in `[0, 1]`), so it can differentially influence `select_mode`
+    /// instead of getting the same default weight for every mode. Modes
+    /// omitted from `weights` fall back to the built-in default.
+    pub fn register_with_weights(&mut self, consideration: Box<dyn Consideration>, weights: HashMap<String, f32>) {
+        self.custom_weights.insert(consideration.name().to_string(), weights);
+        self.considerations.push(consideration);
+    }
+
+    /// Refresh the live `personality_creativity` consideration from
+    /// `personality_adapter.personality_metrics["creativity"]`. Called once
+    /// per `process_cognitive_input` so the consideration reflects the
+    /// agent's actual personality state instead of a hardcoded constant.
+    pub fn set_personality_creativity(&mut self, creativity: f32) {
+        self.personality_creativity.set(creativity);
+    }
+
+    /// Score every candidate `ProcessingMode` and pick the highest-utility one.
+    /// Returns the chosen mode, its score, and a full breakdown for inspection.
+    pub fn select_mode(
+        &self,
+        state: &CognitiveState,
+        input: &CognitiveInput,
+    ) -> (ProcessingMode, f32, Vec<ModeUtility>) {
+        let candidates = [
+            mode_affinity(ProcessingMode::Analytical),
+            mode_affinity(ProcessingMode::Creative),
+            mode_affinity(ProcessingMode::Reactive),
+            mode_affinity(ProcessingMode::Contemplative),
+        ];
+
+        let raw_scores: HashMap<&str, f32> = self
+            .considerations
+            .iter()
+            .map(|c| (c.name(), c.score(state, input).clamp(0.0, 1.0)))
+            .collect();
+
+        let mut breakdown = Vec::with_capacity(candidates.len());
+        let mut best: Option<(ProcessingMode, f32)> = None;
+
+        for affinity in candidates {
+            let per_consideration: Vec<(String, f32)> = self
+                .considerations
+                .iter()
+                .map(|c| {
+                    let base = *raw_scores.get(c.name()).unwrap_or(&0.0);
+                    let weight = affinity.weights.get(c.name()).copied().unwrap_or_else(|| {
+                        self.custom_weights
+                            .get(c.name())
+                            .and_then(|modes| modes.get(mode_name(&affinity.mode)))
+                            .copied()
+                            .unwrap_or(0.1)
+                    });
+                    (c.name().to_string(), weighted_score(base, weight))
+                })
+                .collect();
+
+            let utility = compensated_weighted_product(per_consideration.iter().map(|(_, s)| *s));
+
+            // Strictly-greater keeps tie-breaking deterministic: a later
+            // candidate with an exactly tied score loses to the first
+            // (Analytical) rather than non-deterministically overwriting it.
+            // `weighted_score` blending (below) makes an exact tie across all
+            // four modes vanishingly unlikely outside of all-neutral input.
+            if best.as_ref().map(|(_, s)| utility > *s).unwrap_or(true) {
+                best = Some((affinity.mode.clone(), utility));
+            }
+
+            breakdown.push(ModeUtility {
+                mode: mode_name(&affinity.mode).to_string(),
+                score: utility,
+                consideration_scores: per_consideration.into_iter().collect(),
+            });
+        }
+
+        let (mode, score) = best.unwrap_or((ProcessingMode::Analytical, 0.0));
+        (mode, score, breakdown)
+    }
+}
+
+/// Blend a raw consideration score into a mode-specific weight: `weight ==
+/// 1.0` passes `base` through unchanged, `weight == 0.0` neutralizes the
+/// consideration to `1.0` (it contributes nothing, rather than zeroing the
+/// product). This is what lets a globally-zero consideration (e.g. an empty
+/// memory buffer) differentiate between modes instead of vetoing all of them
+/// identically to 0.0.
+fn weighted_score(base: f32, weight: f32) -> f32 {
+    let weight = weight.clamp(0.0, 1.0);
+    (1.0 - weight + weight * base).clamp(0.0, 1.0)
+}
+
+/// Compensation-corrected weighted product (the Apex utility-AI formula):
+/// multiply the scores, then pull the result back up in proportion to how
+/// many considerations were evaluated, so several mediocre scores don't
+/// compound as harshly as a plain product would. A single score at/near zero
+/// still drags the product towards 0 (a veto for that mode).
+fn compensated_weighted_product(scores: impl Iterator<Item = f32> + Clone) -> f32 {
+    let values: Vec<f32> = scores.map(|s| s.clamp(0.0, 1.0)).collect();
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    let product: f32 = values.iter().product();
+    let modification_factor = 1.0 - (1.0 / values.len() as f32);
+    let makeup_value = (1.0 - product) * modification_factor;
+
+    (product + makeup_value * product).clamp(0.0, 1.0)
+}
+
+fn mode_affinity(mode: ProcessingMode) -> ModeAffinity {
+    let weights = match mode {
+        ProcessingMode::Analytical => [
+            ("arousal", 0.3),
+            ("confidence", 0.9),
+            ("memory_significance", 0.6),
+            ("personality_creativity", 0.2),
+        ],
+        ProcessingMode::Creative => [
+            ("arousal", 0.6),
+            ("confidence", 0.4),
+            ("memory_significance", 0.3),
+            ("personality_creativity", 0.9),
+        ],
+        ProcessingMode::Reactive => [
+            ("arousal", 0.9),
+            ("confidence", 0.3),
+            ("memory_significance", 0.2),
+            ("personality_creativity", 0.2),
+        ],
+        ProcessingMode::Contemplative => [
+            ("arousal", 0.2),
+            ("confidence", 0.6),
+            ("memory_significance", 0.9),
+            ("personality_creativity", 0.5),
+        ],
+    };
+
+    ModeAffinity {
+        mode,
+        weights: weights.into_iter().collect(),
+    }
+}
+
+pub fn mode_name(mode: &ProcessingMode) -> &'static str {
+    match mode {
+        ProcessingMode::Analytical => "analytical",
+        ProcessingMode::Creative => "creative",
+        ProcessingMode::Reactive => "reactive",
+        ProcessingMode::Contemplative => "contemplative",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EmotionalState, MemoryItem};
+
+    fn cognitive_state(memory_buffer: Vec<MemoryItem>) -> CognitiveState {
+        CognitiveState {
+            current_task: None,
+            attention_focus: Vec::new(),
+            memory_buffer,
+            emotional_state: EmotionalState {
+                valence: 0.0,
+                arousal: 0.0,
+                dominance: 0.0,
+                stability: 0.0,
+            },
+            processing_mode: ProcessingMode::Analytical,
+        }
+    }
+
+    fn cognitive_input() -> CognitiveInput {
+        CognitiveInput {
+            sensory_data: Vec::new(),
+            context: String::new(),
+            task_type: String::new(),
+        }
+    }
+
+    #[test]
+    fn weighted_score_neutralizes_at_zero_weight() {
+        // A weight of 0.0 must not veto the mode, regardless of base score.
+        assert_eq!(weighted_score(0.0, 0.0), 1.0);
+        assert_eq!(weighted_score(1.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn weighted_score_passes_through_at_full_weight() {
+        assert_eq!(weighted_score(0.3, 1.0), 0.3);
+    }
+
+    #[test]
+    fn compensated_weighted_product_empty_is_zero() {
+        assert_eq!(compensated_weighted_product(std::iter::empty()), 0.0);
+    }
+
+    #[test]
+    fn compensated_weighted_product_all_zero_scores_is_zero() {
+        // A genuine veto (every consideration at 0) must still drive the
+        // mode's utility to zero.
+        let scores = vec![0.0, 0.0, 0.0, 0.0];
+        assert_eq!(compensated_weighted_product(scores.into_iter()), 0.0);
+    }
+
+    #[test]
+    fn compensated_weighted_product_all_ones_is_one() {
+        let scores = vec![1.0, 1.0, 1.0, 1.0];
+        assert_eq!(compensated_weighted_product(scores.into_iter()), 1.0);
+    }
+
+    #[test]
+    fn empty_memory_buffer_does_not_collapse_every_mode_to_analytical() {
+        // Regression test: `MemorySignificanceConsideration::score` returns
+        // 0.0 on an empty memory buffer. Before `weighted_score` neutralized
+        // zero-weighted considerations, this collapsed every mode to exactly
+        // 0.0 utility and `select_mode` always picked Analytical on ties.
+        let scorer = UtilityScorer::default();
+        let state = cognitive_state(Vec::new());
+        let input = cognitive_input();
+
+        let (_, _, breakdown) = scorer.select_mode(&state, &input);
+        let scores: Vec<f32> = breakdown.iter().map(|m| m.score).collect();
+
+        assert!(
+            scores.iter().any(|&s| (s - scores[0]).abs() > f32::EPSILON),
+            "expected modes to differentiate with an empty memory buffer, got {:?}",
+            scores
+        );
+    }
+
+    #[test]
+    fn select_mode_prefers_reactive_under_high_arousal_low_everything_else() {
+        let scorer = UtilityScorer::default();
+        let state = cognitive_state(Vec::new());
+        let mut high_arousal_state = state.clone();
+        high_arousal_state.emotional_state.arousal = 1.0;
+        let input = cognitive_input();
+
+        let (mode, _, _) = scorer.select_mode(&high_arousal_state, &input);
+        assert_eq!(mode_name(&mode), "reactive");
+    }
+
+    struct ConstantConsideration(f32);
+    impl Consideration for ConstantConsideration {
+        fn name(&self) -> &str {
+            "custom"
+        }
+        fn score(&self, _state: &CognitiveState, _input: &CognitiveInput) -> f32 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn register_with_weights_gives_custom_consideration_real_differential_influence() {
+        // Regression test: a consideration registered with no per-mode
+        // weights got the same default weight for every mode, so it could
+        // never change the arg-max. `register_with_weights` must let it
+        // actually sway `select_mode`. With the built-in considerations alone
+        // this state picks Creative; a zero-scoring consideration weighted
+        // fully for every mode but Reactive vetoes the others.
+        let mut scorer = UtilityScorer::default();
+        let weights = HashMap::from([
+            ("analytical".to_string(), 1.0),
+            ("creative".to_string(), 1.0),
+            ("reactive".to_string(), 0.0),
+            ("contemplative".to_string(), 1.0),
+        ]);
+        scorer.register_with_weights(Box::new(ConstantConsideration(0.0)), weights);
+
+        let state = cognitive_state(Vec::new());
+        let input = cognitive_input();
+
+        let (mode, _, _) = scorer.select_mode(&state, &input);
+        assert_eq!(mode_name(&mode), "reactive");
+    }
+
+    #[test]
+    fn register_without_weights_still_falls_back_to_default_weight() {
+        let mut scorer = UtilityScorer::default();
+        scorer.register(Box::new(ConstantConsideration(0.0)));
+
+        let state = cognitive_state(Vec::new());
+        let input = cognitive_input();
+
+        // Should not panic, should apply the same default weight to every
+        // mode, and should still produce a full breakdown.
+        let (_, _, breakdown) = scorer.select_mode(&state, &input);
+        assert!(breakdown.iter().any(|m| m.consideration_scores.contains_key("custom")));
+        assert!(breakdown
+            .iter()
+            .all(|m| m.consideration_scores.get("custom") == Some(&weighted_score(0.0, 0.1))));
+    }
+}
@@ -1,34 +1,80 @@
 
 use wasm_bindgen::prelude::*;
-use web_sys::console;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-// Import console.log for debugging
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = console)]
-    fn log(s: &str);
-}
+mod tools;
+use tools::ToolRegistry;
 
-macro_rules! console_log {
-    ($($t:tt)*) => (log(&format_args!($($t)*).to_string()))
-}
+mod lora;
+use lora::LoraRegistry;
+
+mod templating;
+
+mod snapshot;
+use snapshot::AgentSnapshot;
 
-// Initialize panic hook
+mod logging;
+
+mod skill;
+use skill::SkillRegistry;
+
+// `wee_alloc` trades allocation speed for code size; enable the "wee_alloc"
+// feature for production bundles where download size matters more than
+// allocator throughput.
+#[cfg(feature = "wee_alloc")]
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+// Initialize the panic hook and the console-backed `log` facade. The
+// "release" feature drops the panic hook and caps logging at "warn" to
+// shrink the shipped bundle; the logger itself is always installed.
 #[wasm_bindgen(start)]
 pub fn main() {
+    #[cfg(not(feature = "release"))]
     console_error_panic_hook::set_once();
-    console_log!("Agent-Core WASM initialized: {}", "test-agent-init");
+
+    logging::init();
+    log::info!("Agent-Core WASM initialized: {}", "test-agent-init");
+}
+
+/// Set the `log` facade's max level ("trace"/"debug"/"info"/"warn"/"error"/"off"),
+/// so the host can dial verbosity per environment.
+#[wasm_bindgen]
+pub fn set_log_level(level: &str) -> bool {
+    logging::set_level(level)
 }
 
 // Agent configuration
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct AgentConfig {
     pub agent_id: String,
     pub max_context_size: usize,
     pub learning_rate: f32,
     pub adaptation_threshold: f32,
     pub skill_timeout: u32,
+    // `{{var}}` template rendered into the resolved system prompt at init time.
+    #[serde(default = "AgentConfig::default_system_prompt_template")]
+    pub system_prompt_template: String,
+    // `{{var}}` template the response is rendered through in `process_cognitive_input`.
+    #[serde(default = "AgentConfig::default_response_template")]
+    pub response_template: String,
+    // Variable values substituted into the templates above and tool descriptions.
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    // Fallback values used when a template variable isn't supplied.
+    #[serde(default)]
+    pub variable_defaults: HashMap<String, String>,
+}
+
+impl AgentConfig {
+    fn default_system_prompt_template() -> String {
+        "You are {{agent_name}}, an agent-core instance.".to_string()
+    }
+
+    fn default_response_template() -> String {
+        "Processed: {{input}}".to_string()
+    }
 }
 
 // Agent-Core implementation
@@ -37,6 +83,31 @@ pub struct AgentCore {
     config: AgentConfig,
     initialized: bool,
     memory: Vec<String>,
+    tools: ToolRegistry,
+    lora_adapters: LoraRegistry,
+    skills: SkillRegistry,
+    // System prompt with `{{var}}` placeholders resolved at construction time.
+    resolved_system_prompt: String,
+}
+
+/// Allocate `size` bytes in WASM linear memory so the host can write raw
+/// LoRA adapter bytes directly in, without a base64 round-trip through JS.
+#[wasm_bindgen]
+pub fn alloc(size: usize) -> *mut u8 {
+    let mut buffer = Vec::with_capacity(size);
+    let ptr = buffer.as_mut_ptr();
+    std::mem::forget(buffer);
+    ptr
+}
+
+/// Free a buffer previously returned by `alloc`.
+///
+/// # Safety
+/// `ptr`/`size` must be a pointer/capacity pair previously returned by `alloc`
+/// and not already freed.
+#[wasm_bindgen]
+pub unsafe fn dealloc(ptr: *mut u8, size: usize) {
+    let _ = Vec::from_raw_parts(ptr, 0, size);
 }
 
 #[wasm_bindgen]
@@ -49,13 +120,74 @@ impl AgentCore {
             learning_rate: 0.01,
             adaptation_threshold: 0.7,
             skill_timeout: 30000,
+            system_prompt_template: AgentConfig::default_system_prompt_template(),
+            response_template: AgentConfig::default_response_template(),
+            variables: HashMap::new(),
+            variable_defaults: HashMap::from([("agent_name".to_string(), "Test Agent Init".to_string())]),
         };
 
+        Self::from_config(config)
+    }
+
+    /// Construct an `AgentCore` from a JSON-serialized `AgentConfig`, so the
+    /// same compiled agent can be reused with different personas/endpoints by
+    /// passing a different `variables` map at init time.
+    #[wasm_bindgen]
+    pub fn init(config_json: &str) -> Result<AgentCore, JsValue> {
+        let config: AgentConfig = serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("invalid agent config: {}", e)))?;
+        Ok(Self::from_config(config))
+    }
+
+    fn from_config(config: AgentConfig) -> AgentCore {
+        let resolved_system_prompt =
+            templating::render(&config.system_prompt_template, &config.variables, &config.variable_defaults);
+
         AgentCore {
             config,
             initialized: false,
             memory: Vec::new(),
+            tools: ToolRegistry::default(),
+            lora_adapters: LoraRegistry::default(),
+            skills: SkillRegistry::default(),
+            resolved_system_prompt,
+        }
+    }
+
+    /// Register a tool's JSON Schema so `agent_core_execute_tool` calls
+    /// against it are validated instead of dispatched unconditionally.
+    #[wasm_bindgen]
+    pub fn register_tool(&mut self, name: &str, json_schema: &str) -> bool {
+        let rendered_schema = self.render_tool_description(json_schema);
+        match self.tools.register(name, &rendered_schema) {
+            Ok(()) => true,
+            Err(error) => {
+                log::warn!("Failed to register tool '{}': {}", name, error);
+                false
+            }
+        }
+    }
+
+    // Substitute `{{var}}` placeholders in a tool schema's top-level
+    // "description" field using the agent's configured variables.
+    fn render_tool_description(&self, json_schema: &str) -> String {
+        let Ok(mut schema) = serde_json::from_str::<serde_json::Value>(json_schema) else {
+            return json_schema.to_string();
+        };
+
+        if let Some(description) = schema.get("description").and_then(|d| d.as_str()) {
+            let rendered = templating::render(description, &self.config.variables, &self.config.variable_defaults);
+            schema["description"] = serde_json::Value::String(rendered);
         }
+
+        schema.to_string()
+    }
+
+    /// Emit the full registered-tool schema catalog as JSON, so a host LLM
+    /// can do structured function calling against it.
+    #[wasm_bindgen]
+    pub fn list_tools(&self) -> String {
+        self.tools.list_tools_json()
     }
 
     #[wasm_bindgen]
@@ -64,7 +196,11 @@ impl AgentCore {
             return r#"{"error": "Agent not initialized"}"#.to_string();
         }
 
-        console_log!("Executing agent-core with input: {}", input);
+        log::debug!("Executing agent-core with input: {}", input);
+
+        if let Some(skill) = self.skills.match_input(input) {
+            log::debug!("Input matched installed skill '{}'", skill.name);
+        }
 
         // Parse input and context
         let result = self.process_cognitive_input(input, context);
@@ -79,43 +215,141 @@ impl AgentCore {
     }
 
     #[wasm_bindgen]
-    pub fn agent_core_execute_tool(&self, tool_name: &str, parameters: &str, context: &str) -> String {
+    pub fn agent_core_execute_tool(&self, tool_name: &str, parameters: &str, _context: &str) -> String {
         if !self.initialized {
             return r#"{"error": "Agent not initialized"}"#.to_string();
         }
 
-        console_log!("Executing tool: {} with parameters: {}", tool_name, parameters);
+        log::debug!("Executing tool: {} with parameters: {}", tool_name, parameters);
+
+        let validated_parameters = match self.tools.validate(tool_name, parameters) {
+            Ok(parameters) => parameters,
+            Err(failure) => return failure.to_json(),
+        };
 
-        // Tool execution logic
         format!(
             r#"{{"success": true, "result": "Tool {} executed successfully", "parameters": {}, "agentId": "{}"}}"#,
-            tool_name, parameters, self.config.agent_id
+            tool_name, validated_parameters, self.config.agent_id
         )
     }
 
+    /// Load a LoRA adapter from bytes already written into linear memory at
+    /// `(ptr, len)` by the host via `alloc`, avoiding a JS-string round-trip.
+    ///
+    /// # Safety
+    /// `ptr`/`len` must describe a valid, initialized region of this
+    /// module's linear memory, e.g. one previously returned by `alloc`.
+    #[wasm_bindgen]
+    pub unsafe fn agent_core_load_lora(&mut self, name: &str, ptr: *const u8, len: usize) -> bool {
+        log::debug!("Loading LoRA adapter '{}' from {} bytes at {:p}", name, len, ptr);
+        let bytes = std::slice::from_raw_parts(ptr, len);
+        self.agent_core_load_lora_bytes(name, bytes)
+    }
+
+    /// Load a LoRA adapter from an owned byte slice (for hosts that already
+    /// have the bytes on the Rust/wasm-bindgen side rather than raw memory).
+    #[wasm_bindgen]
+    pub fn agent_core_load_lora_bytes(&mut self, name: &str, bytes: &[u8]) -> bool {
+        match self.lora_adapters.load(name, bytes) {
+            Ok(()) => true,
+            Err(error) => {
+                log::warn!("Failed to load LoRA adapter '{}': {}", name, error);
+                false
+            }
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn agent_core_unload_lora(&mut self, name: &str) -> bool {
+        self.lora_adapters.unload(name)
+    }
+
     #[wasm_bindgen]
-    pub fn agent_core_load_lora(&mut self, adapter: &str) -> bool {
-        console_log!("Loading LoRA adapter: {}", adapter);
-        // LoRA adapter loading logic would go here
-        true
+    pub fn agent_core_list_lora(&self) -> String {
+        serde_json::to_string(&self.lora_adapters.list_names()).unwrap_or_else(|_| "[]".to_string())
     }
 
+    /// Decode and install a `Skill` protobuf message, rejecting malformed or
+    /// unsupported-version payloads instead of accepting any byte buffer.
     #[wasm_bindgen]
     pub fn agent_core_apply_skill(&mut self, proto_bytes: &[u8]) -> bool {
-        console_log!("Applying skill from protobuf ({} bytes)", proto_bytes.len());
-        // Skill application logic would go here
-        true
+        log::debug!("Applying skill from protobuf ({} bytes)", proto_bytes.len());
+        match self.skills.install(proto_bytes) {
+            Ok(skill) => {
+                log::info!("Installed skill '{}' (v{})", skill.name, skill.version);
+                true
+            }
+            Err(error) => {
+                log::warn!("Failed to apply skill: {}", error);
+                false
+            }
+        }
+    }
+
+    /// Serialize the full agent state (config, memory ring buffer, loaded
+    /// LoRA adapters, and installed skills) into a versioned blob, so it can
+    /// be moved between Web Workers or persisted across a page reload and
+    /// rehydrated with `restore`.
+    #[wasm_bindgen]
+    pub fn snapshot(&self) -> Result<Vec<u8>, JsValue> {
+        AgentSnapshot::new(
+            &self.config,
+            self.initialized,
+            &self.memory,
+            self.lora_adapters.adapters().to_vec(),
+            self.skills.skills().to_vec(),
+        )
+        .encode()
+        .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Rehydrate an `AgentCore` from a blob previously produced by `snapshot`,
+    /// including its loaded LoRA adapters and installed skills. Registered
+    /// tools are not part of the snapshot and must be re-registered by the
+    /// host after restoring.
+    #[wasm_bindgen]
+    pub fn restore(bytes: &[u8]) -> Result<AgentCore, JsValue> {
+        let snapshot = AgentSnapshot::decode(bytes).map_err(|e| JsValue::from_str(&e))?;
+        let mut agent = Self::from_config(snapshot.config);
+        agent.initialized = snapshot.initialized;
+        agent.memory = snapshot.memory;
+        agent.lora_adapters = LoraRegistry::restore(snapshot.lora_adapters);
+        agent.skills = SkillRegistry::restore(snapshot.skills);
+        Ok(agent)
+    }
+
+    /// The system prompt with `{{var}}` placeholders resolved from `config.variables`.
+    #[wasm_bindgen]
+    pub fn get_system_prompt(&self) -> String {
+        self.resolved_system_prompt.clone()
     }
 
     #[wasm_bindgen]
     pub fn agent_core_get_status(&self) -> String {
+        let installed_skills: Vec<String> = self
+            .skills
+            .installed_summaries()
+            .into_iter()
+            .map(|(name, version)| format!("{}@{}", name, version))
+            .collect();
+
         format!(
-            r#"{{"agentId": "{}", "agentName": "Test Agent Init", "version": "1.0.0", "initialized": {}, "cognitiveEngine": "rust-wasm", "availableTools": [], "memorySize": {}}}"#,
-            self.config.agent_id, self.initialized, self.memory.len()
+            r#"{{"agentId": "{}", "agentName": "Test Agent Init", "version": "1.0.0", "initialized": {}, "cognitiveEngine": "rust-wasm", "availableTools": [], "memorySize": {}, "activeLoraAdapters": {}, "installedSkills": {}}}"#,
+            self.config.agent_id,
+            self.initialized,
+            self.memory.len(),
+            self.agent_core_list_lora(),
+            serde_json::to_string(&installed_skills).unwrap_or_else(|_| "[]".to_string())
         )
     }
 }
 
+impl Default for AgentCore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AgentCore {
     fn process_cognitive_input(&self, input: &str, context: &str) -> String {
         // Cognitive processing logic
@@ -123,9 +357,18 @@ impl AgentCore {
 
         let confidence = if input.len() > 10 { 0.8 } else { 0.6 };
 
+        let mut render_variables = self.config.variables.clone();
+        render_variables.insert("input".to_string(), input.to_string());
+        render_variables.insert("context".to_string(), context.to_string());
+        let response =
+            templating::render(&self.config.response_template, &render_variables, &self.config.variable_defaults);
+
         format!(
-            r#"{{"success": true, "result": {{"response": "Processed: {}", "confidence": {}, "source": "rust-agent-core"}}, "processingTime": 50, "metadata": {{"agentId": "{}", "contextSize": {}}}}}"#,
-            input, confidence, self.config.agent_id, context.len()
+            r#"{{"success": true, "result": {{"response": {}, "confidence": {}, "source": "rust-agent-core"}}, "processingTime": 50, "metadata": {{"agentId": "{}", "contextSize": {}}}}}"#,
+            serde_json::to_string(&response).unwrap_or_default(),
+            confidence,
+            self.config.agent_id,
+            context.len()
         )
     }
 }
@@ -0,0 +1,71 @@
+// `log` facade backend that routes leveled records to `console.debug/info/warn/error`,
+// so the host can dial verbosity per environment instead of getting
+// unconditional `console.log` prints.
+
+use log::{Level, Log, Metadata, Record};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console, js_name = debug)]
+    fn console_debug(s: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = info)]
+    fn console_info(s: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = warn)]
+    fn console_warn(s: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = error)]
+    fn console_error(s: &str);
+}
+
+struct ConsoleLogger;
+
+impl Log for ConsoleLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let message = format!("[{}] {}", record.target(), record.args());
+        match record.level() {
+            Level::Error => console_error(&message),
+            Level::Warn => console_warn(&message),
+            Level::Info => console_info(&message),
+            Level::Debug | Level::Trace => console_debug(&message),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: ConsoleLogger = ConsoleLogger;
+
+/// Install the console-backed `log` facade. Safe to call more than once.
+///
+/// Defaults to `Info` verbosity, or `Warn` under the "release" feature so
+/// production bundles don't pay for formatting debug/info strings they'll
+/// never show a user.
+pub fn init() {
+    // `set_logger` errors only if a logger is already installed, which is
+    // fine here: a repeated call keeps the logger that is already in place.
+    let _ = log::set_logger(&LOGGER);
+
+    #[cfg(feature = "release")]
+    log::set_max_level(log::LevelFilter::Warn);
+    #[cfg(not(feature = "release"))]
+    log::set_max_level(log::LevelFilter::Info);
+}
+
+/// Parse a level name ("trace"/"debug"/"info"/"warn"/"error"/"off") and set
+/// it as the facade's max level, so the host can dial verbosity at runtime.
+pub fn set_level(level: &str) -> bool {
+    match level.to_lowercase().parse::<log::LevelFilter>() {
+        Ok(filter) => {
+            log::set_max_level(filter);
+            true
+        }
+        Err(_) => false,
+    }
+}
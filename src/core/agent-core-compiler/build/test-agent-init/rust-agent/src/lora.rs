@@ -0,0 +1,204 @@
+// Binary LoRA adapter loading: the host writes raw adapter bytes directly
+// into WASM linear memory via `alloc`/`dealloc` instead of base64-encoding
+// them through a JS string. Each parsed adapter keeps its own copy of the bytes.
+
+use serde::{Deserialize, Serialize};
+
+/// Per-layer LoRA weight offsets within the adapter's byte buffer.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LoraLayerOffset {
+    pub layer_name: String,
+    pub a_offset: usize,
+    pub a_len: usize,
+    pub b_offset: usize,
+    pub b_len: usize,
+}
+
+/// A parsed LoRA adapter: rank/alpha plus per-layer weight offsets into the
+/// original byte buffer, which is kept around rather than copied into tensors.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LoraAdapter {
+    pub name: String,
+    pub rank: u32,
+    pub alpha: f32,
+    pub layers: Vec<LoraLayerOffset>,
+    bytes: Vec<u8>,
+}
+
+impl LoraAdapter {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Parse a LoRA adapter header + weight blob:
+/// `[rank: u32][alpha: f32][layer_count: u32]` followed by, per layer,
+/// `[name_len: u32][name: utf8][a_offset: u32][a_len: u32][b_offset: u32][b_len: u32]`,
+/// with the weight offsets relative to the start of `bytes`.
+pub fn parse_adapter(name: &str, bytes: &[u8]) -> Result<LoraAdapter, String> {
+    const HEADER_LEN: usize = 4 + 4 + 4;
+    if bytes.len() < HEADER_LEN {
+        return Err("lora: buffer too small for header".to_string());
+    }
+
+    let rank = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+    let alpha = f32::from_le_bytes(bytes[4..8].try_into().unwrap());
+    let layer_count = u32::from_le_bytes(bytes[8..12].try_into().unwrap()) as usize;
+
+    let mut cursor = HEADER_LEN;
+    let mut layers = Vec::with_capacity(layer_count);
+
+    for _ in 0..layer_count {
+        let name_len = read_u32(bytes, cursor)? as usize;
+        cursor += 4;
+        let layer_name = std::str::from_utf8(read_slice(bytes, cursor, name_len)?)
+            .map_err(|e| format!("lora: layer name is not valid UTF-8: {}", e))?
+            .to_string();
+        cursor += name_len;
+
+        let a_offset = read_u32(bytes, cursor)? as usize;
+        cursor += 4;
+        let a_len = read_u32(bytes, cursor)? as usize;
+        cursor += 4;
+        let b_offset = read_u32(bytes, cursor)? as usize;
+        cursor += 4;
+        let b_len = read_u32(bytes, cursor)? as usize;
+        cursor += 4;
+
+        // `usize` is 32-bit on the wasm32 deploy target, so unchecked addition
+        // of attacker/garbage u32 offsets near u32::MAX could wrap and slip
+        // past a plain `>` comparison; checked_add catches that instead.
+        let a_out_of_bounds = a_offset.checked_add(a_len).is_none_or(|end| end > bytes.len());
+        let b_out_of_bounds = b_offset.checked_add(b_len).is_none_or(|end| end > bytes.len());
+        if a_out_of_bounds || b_out_of_bounds {
+            return Err(format!("lora: layer '{}' weight offsets out of bounds", layer_name));
+        }
+
+        layers.push(LoraLayerOffset {
+            layer_name,
+            a_offset,
+            a_len,
+            b_offset,
+            b_len,
+        });
+    }
+
+    Ok(LoraAdapter {
+        name: name.to_string(),
+        rank,
+        alpha,
+        layers,
+        bytes: bytes.to_vec(),
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, String> {
+    let slice = read_slice(bytes, offset, 4)?;
+    Ok(u32::from_le_bytes(slice.try_into().unwrap()))
+}
+
+fn read_slice(bytes: &[u8], offset: usize, len: usize) -> Result<&[u8], String> {
+    bytes
+        .get(offset..offset + len)
+        .ok_or_else(|| "lora: header field out of bounds".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_adapter(rank: u32, alpha: f32, layers: &[(&str, u32, u32, u32, u32)]) -> Vec<u8> {
+        let mut bytes = rank.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&alpha.to_le_bytes());
+        bytes.extend_from_slice(&(layers.len() as u32).to_le_bytes());
+        for (name, a_offset, a_len, b_offset, b_len) in layers {
+            bytes.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(name.as_bytes());
+            bytes.extend_from_slice(&a_offset.to_le_bytes());
+            bytes.extend_from_slice(&a_len.to_le_bytes());
+            bytes.extend_from_slice(&b_offset.to_le_bytes());
+            bytes.extend_from_slice(&b_len.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn parse_adapter_rejects_buffer_too_small_for_header() {
+        let err = parse_adapter("a", &[0u8; 4]).unwrap_err();
+        assert!(err.contains("too small"));
+    }
+
+    #[test]
+    fn parse_adapter_rejects_truncated_layer_name() {
+        // Header advertises one layer, but only the name-length prefix is
+        // present — the name bytes it claims don't exist in the buffer.
+        let mut header = 4u32.to_le_bytes().to_vec();
+        header.extend_from_slice(&1.0f32.to_le_bytes());
+        header.extend_from_slice(&1u32.to_le_bytes());
+        header.extend_from_slice(&100u32.to_le_bytes());
+        let err = parse_adapter("a", &header).unwrap_err();
+        assert!(err.contains("out of bounds"));
+    }
+
+    #[test]
+    fn parse_adapter_rejects_out_of_bounds_weight_offsets() {
+        let mut bytes = build_adapter(4, 1.0, &[("layer0", 0, 1000, 0, 1000)]);
+        bytes.extend_from_slice(&[0u8; 8]); // far too little weight data for the claimed offsets
+        let err = parse_adapter("a", &bytes).unwrap_err();
+        assert!(err.contains("out of bounds"));
+    }
+
+    #[test]
+    fn parse_adapter_accepts_well_formed_buffer() {
+        let mut bytes = build_adapter(4, 1.0, &[("layer0", 0, 4, 4, 4)]);
+        bytes.extend_from_slice(&[0u8; 8]);
+        let adapter = parse_adapter("test", &bytes).unwrap();
+        assert_eq!(adapter.name(), "test");
+        assert_eq!(adapter.rank, 4);
+        assert_eq!(adapter.layers.len(), 1);
+    }
+
+    #[test]
+    fn registry_unload_reports_whether_an_adapter_was_removed() {
+        let mut registry = LoraRegistry::default();
+        let mut bytes = build_adapter(4, 1.0, &[("layer0", 0, 4, 4, 4)]);
+        bytes.extend_from_slice(&[0u8; 8]);
+        registry.load("test", &bytes).unwrap();
+
+        assert!(registry.unload("test"));
+        assert!(!registry.unload("test"));
+    }
+}
+
+#[derive(Default)]
+pub struct LoraRegistry {
+    adapters: Vec<LoraAdapter>,
+}
+
+impl LoraRegistry {
+    pub fn load(&mut self, name: &str, bytes: &[u8]) -> Result<(), String> {
+        let adapter = parse_adapter(name, bytes)?;
+        self.adapters.push(adapter);
+        Ok(())
+    }
+
+    pub fn unload(&mut self, name: &str) -> bool {
+        let before = self.adapters.len();
+        self.adapters.retain(|a| a.name() != name);
+        self.adapters.len() != before
+    }
+
+    pub fn list_names(&self) -> Vec<String> {
+        self.adapters.iter().map(|a| a.name().to_string()).collect()
+    }
+
+    /// The full set of loaded adapters, for inclusion in an `AgentSnapshot`.
+    pub fn adapters(&self) -> &[LoraAdapter] {
+        &self.adapters
+    }
+
+    /// Rehydrate a registry from adapters previously returned by `adapters()`.
+    pub fn restore(adapters: Vec<LoraAdapter>) -> Self {
+        LoraRegistry { adapters }
+    }
+}
@@ -0,0 +1,167 @@
+// `Skill` protobuf message and decoding for `agent_core_apply_skill`, in
+// place of logging the byte count and accepting any payload unconditionally.
+
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+/// Current supported `Skill` protobuf schema version.
+const SUPPORTED_VERSION: u32 = 1;
+
+#[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+pub struct Skill {
+    #[prost(uint32, tag = "1")]
+    pub version: u32,
+    #[prost(string, tag = "2")]
+    pub id: String,
+    #[prost(string, tag = "3")]
+    pub name: String,
+    #[prost(string, repeated, tag = "4")]
+    pub trigger_patterns: Vec<String>,
+    #[prost(string, repeated, tag = "5")]
+    pub required_tools: Vec<String>,
+    #[prost(string, tag = "6")]
+    pub parameter_schema_json: String,
+    /// Offset of embedded bytecode/weights within a side-channel blob, if any (0 = none).
+    #[prost(uint64, tag = "7")]
+    pub bytecode_offset: u64,
+}
+
+/// Decode and validate a `Skill` protobuf message, rejecting unsupported
+/// versions or missing required fields.
+pub fn decode(proto_bytes: &[u8]) -> Result<Skill, String> {
+    let skill = Skill::decode(proto_bytes).map_err(|e| format!("skill: malformed protobuf: {}", e))?;
+
+    if skill.version == 0 || skill.version > SUPPORTED_VERSION {
+        return Err(format!(
+            "skill: unsupported schema version {} (supported: {})",
+            skill.version, SUPPORTED_VERSION
+        ));
+    }
+    if skill.id.is_empty() {
+        return Err("skill: missing required field 'id'".to_string());
+    }
+    if skill.name.is_empty() {
+        return Err("skill: missing required field 'name'".to_string());
+    }
+
+    Ok(skill)
+}
+
+#[derive(Default)]
+pub struct SkillRegistry {
+    skills: Vec<Skill>,
+}
+
+impl SkillRegistry {
+    pub fn install(&mut self, proto_bytes: &[u8]) -> Result<&Skill, String> {
+        let skill = decode(proto_bytes)?;
+        self.skills.retain(|s| s.id != skill.id);
+        self.skills.push(skill);
+        Ok(self.skills.last().unwrap())
+    }
+
+    /// Find the first installed skill whose trigger pattern matches `input`
+    /// as a plain substring.
+    pub fn match_input<'a>(&'a self, input: &str) -> Option<&'a Skill> {
+        self.skills
+            .iter()
+            .find(|skill| skill.trigger_patterns.iter().any(|pattern| input.contains(pattern.as_str())))
+    }
+
+    pub fn installed_summaries(&self) -> Vec<(String, u32)> {
+        self.skills.iter().map(|s| (s.name.clone(), s.version)).collect()
+    }
+
+    /// The full set of installed skills, for inclusion in an `AgentSnapshot`.
+    pub fn skills(&self) -> &[Skill] {
+        &self.skills
+    }
+
+    /// Rehydrate a registry from skills previously returned by `skills()`.
+    pub fn restore(skills: Vec<Skill>) -> Self {
+        SkillRegistry { skills }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_skill() -> Skill {
+        Skill {
+            version: 1,
+            id: "skill-1".to_string(),
+            name: "Test Skill".to_string(),
+            trigger_patterns: vec!["hello".to_string()],
+            required_tools: Vec::new(),
+            parameter_schema_json: "{}".to_string(),
+            bytecode_offset: 0,
+        }
+    }
+
+    #[test]
+    fn decode_rejects_malformed_protobuf() {
+        let err = decode(&[0xff, 0xff, 0xff]).unwrap_err();
+        assert!(err.contains("malformed protobuf"));
+    }
+
+    #[test]
+    fn decode_rejects_unsupported_version() {
+        let mut skill = valid_skill();
+        skill.version = 2;
+        let err = decode(&skill.encode_to_vec()).unwrap_err();
+        assert!(err.contains("unsupported schema version"));
+    }
+
+    #[test]
+    fn decode_rejects_zero_version() {
+        let mut skill = valid_skill();
+        skill.version = 0;
+        let err = decode(&skill.encode_to_vec()).unwrap_err();
+        assert!(err.contains("unsupported schema version"));
+    }
+
+    #[test]
+    fn decode_rejects_missing_id() {
+        let mut skill = valid_skill();
+        skill.id = String::new();
+        let err = decode(&skill.encode_to_vec()).unwrap_err();
+        assert!(err.contains("'id'"));
+    }
+
+    #[test]
+    fn decode_rejects_missing_name() {
+        let mut skill = valid_skill();
+        skill.name = String::new();
+        let err = decode(&skill.encode_to_vec()).unwrap_err();
+        assert!(err.contains("'name'"));
+    }
+
+    #[test]
+    fn decode_accepts_well_formed_skill() {
+        let skill = decode(&valid_skill().encode_to_vec()).unwrap();
+        assert_eq!(skill.id, "skill-1");
+    }
+
+    #[test]
+    fn install_replaces_existing_skill_with_same_id() {
+        let mut registry = SkillRegistry::default();
+        registry.install(&valid_skill().encode_to_vec()).unwrap();
+
+        let mut updated = valid_skill();
+        updated.name = "Updated Skill".to_string();
+        registry.install(&updated.encode_to_vec()).unwrap();
+
+        assert_eq!(registry.skills().len(), 1);
+        assert_eq!(registry.skills()[0].name, "Updated Skill");
+    }
+
+    #[test]
+    fn match_input_finds_skill_by_trigger_pattern() {
+        let mut registry = SkillRegistry::default();
+        registry.install(&valid_skill().encode_to_vec()).unwrap();
+
+        assert!(registry.match_input("hello there").is_some());
+        assert!(registry.match_input("goodbye").is_none());
+    }
+}
@@ -0,0 +1,56 @@
+// Versioned snapshot/restore of the full agent state (config, memory ring
+// buffer, loaded LoRA adapters, and installed skills), so a live `AgentCore`
+// can be moved between Web Workers or persisted across a page reload without
+// losing cognitive context or needing the host to re-load adapters/skills.
+
+use crate::lora::LoraAdapter;
+use crate::skill::Skill;
+use crate::AgentConfig;
+use serde::{Deserialize, Serialize};
+
+const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+pub struct AgentSnapshot {
+    pub version: u32,
+    pub config: AgentConfig,
+    pub initialized: bool,
+    pub memory: Vec<String>,
+    pub lora_adapters: Vec<LoraAdapter>,
+    pub skills: Vec<Skill>,
+}
+
+impl AgentSnapshot {
+    pub fn new(
+        config: &AgentConfig,
+        initialized: bool,
+        memory: &[String],
+        lora_adapters: Vec<LoraAdapter>,
+        skills: Vec<Skill>,
+    ) -> Self {
+        AgentSnapshot {
+            version: SNAPSHOT_VERSION,
+            config: config.clone(),
+            initialized,
+            memory: memory.to_vec(),
+            lora_adapters,
+            skills,
+        }
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>, String> {
+        bincode::serialize(self).map_err(|e| format!("snapshot: failed to encode: {}", e))
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let snapshot: AgentSnapshot =
+            bincode::deserialize(bytes).map_err(|e| format!("snapshot: failed to decode: {}", e))?;
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(format!(
+                "snapshot: unsupported version {} (expected {})",
+                snapshot.version, SNAPSHOT_VERSION
+            ));
+        }
+        Ok(snapshot)
+    }
+}
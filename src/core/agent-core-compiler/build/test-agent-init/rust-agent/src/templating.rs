@@ -0,0 +1,76 @@
+// `{{var}}` placeholder substitution for agent prompt/response templates,
+// resolved from a variables map at construction so a compiled agent can be
+// reused with different personas/endpoints without recompiling.
+
+use std::collections::HashMap;
+
+/// Substitute `{{var}}` placeholders in `template` using `variables`, falling
+/// back to `defaults` when a variable isn't supplied.
+pub fn render(template: &str, variables: &HashMap<String, String>, defaults: &HashMap<String, String>) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+
+        let Some(end) = after_open.find("}}") else {
+            // Unterminated placeholder: emit the rest verbatim.
+            rendered.push_str(&rest[start..]);
+            return rendered;
+        };
+
+        let var_name = after_open[..end].trim();
+        let value = variables
+            .get(var_name)
+            .or_else(|| defaults.get(var_name))
+            .cloned()
+            .unwrap_or_default();
+        rendered.push_str(&value);
+
+        rest = &after_open[end + 2..];
+    }
+
+    rendered.push_str(rest);
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_from_variables() {
+        let variables = HashMap::from([("name".to_string(), "Ada".to_string())]);
+        let defaults = HashMap::new();
+        assert_eq!(render("hello {{name}}", &variables, &defaults), "hello Ada");
+    }
+
+    #[test]
+    fn falls_back_to_defaults_when_variable_missing() {
+        let variables = HashMap::new();
+        let defaults = HashMap::from([("name".to_string(), "Default Agent".to_string())]);
+        assert_eq!(render("hello {{name}}", &variables, &defaults), "hello Default Agent");
+    }
+
+    #[test]
+    fn renders_empty_string_when_variable_and_default_missing() {
+        let variables = HashMap::new();
+        let defaults = HashMap::new();
+        assert_eq!(render("hello {{name}}", &variables, &defaults), "hello ");
+    }
+
+    #[test]
+    fn leaves_unterminated_placeholder_verbatim() {
+        let variables = HashMap::new();
+        let defaults = HashMap::new();
+        assert_eq!(render("hello {{name", &variables, &defaults), "hello {{name");
+    }
+
+    #[test]
+    fn trims_whitespace_inside_placeholder() {
+        let variables = HashMap::from([("name".to_string(), "Ada".to_string())]);
+        let defaults = HashMap::new();
+        assert_eq!(render("hello {{ name }}", &variables, &defaults), "hello Ada");
+    }
+}
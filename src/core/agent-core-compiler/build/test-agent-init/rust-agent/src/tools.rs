@@ -0,0 +1,153 @@
+// Schema-validated tool-calling registry: each registered tool carries a
+// JSON Schema for its arguments, and call parameters are validated against
+// it. No tool implementation is invoked; a call that passes validation gets
+// a fixed success message echoing the validated parameters.
+
+use jsonschema::JSONSchema;
+use serde_json::Value;
+use std::collections::HashMap;
+
+pub struct ToolDef {
+    pub schema: Value,
+    compiled_schema: JSONSchema,
+}
+
+impl ToolDef {
+    fn new(name: &str, schema: Value) -> Result<Self, String> {
+        let compiled_schema = JSONSchema::compile(&schema)
+            .map_err(|e| format!("invalid JSON schema for tool '{}': {}", name, e))?;
+        Ok(ToolDef {
+            schema,
+            compiled_schema,
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct ToolRegistry {
+    tools: HashMap<String, ToolDef>,
+}
+
+impl ToolRegistry {
+    pub fn register(&mut self, name: &str, schema_json: &str) -> Result<(), String> {
+        let schema: Value = serde_json::from_str(schema_json)
+            .map_err(|e| format!("invalid JSON schema for tool '{}': {}", name, e))?;
+        let tool = ToolDef::new(name, schema)?;
+        self.tools.insert(name.to_string(), tool);
+        Ok(())
+    }
+
+    /// Validate `parameters_json` against the registered tool's schema,
+    /// returning the parsed arguments on success or the list of validation
+    /// error messages on failure.
+    pub fn validate(&self, tool_name: &str, parameters_json: &str) -> Result<Value, ValidationFailure> {
+        let tool = self
+            .tools
+            .get(tool_name)
+            .ok_or_else(|| ValidationFailure::unknown_tool(tool_name))?;
+
+        let parameters: Value = serde_json::from_str(parameters_json)
+            .map_err(|e| ValidationFailure::malformed_json(e.to_string()))?;
+
+        let validation_errors: Vec<String> = tool
+            .compiled_schema
+            .validate(&parameters)
+            .err()
+            .map(|errors| errors.map(|e| e.to_string()).collect())
+            .unwrap_or_default();
+
+        if validation_errors.is_empty() {
+            Ok(parameters)
+        } else {
+            Err(ValidationFailure {
+                error: format!("parameters for '{}' failed schema validation", tool_name),
+                validation_errors,
+            })
+        }
+    }
+
+    pub fn list_tools_json(&self) -> String {
+        let catalog: Vec<&Value> = self.tools.values().map(|t| &t.schema).collect();
+        serde_json::to_string(&catalog).unwrap_or_else(|_| "[]".to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct ValidationFailure {
+    pub error: String,
+    pub validation_errors: Vec<String>,
+}
+
+impl ValidationFailure {
+    fn unknown_tool(name: &str) -> Self {
+        ValidationFailure {
+            error: format!("unknown tool '{}'", name),
+            validation_errors: Vec::new(),
+        }
+    }
+
+    fn malformed_json(reason: String) -> Self {
+        ValidationFailure {
+            error: format!("malformed parameters JSON: {}", reason),
+            validation_errors: Vec::new(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"error\": {}, \"validationErrors\": {}}}",
+            serde_json::to_string(&self.error).unwrap_or_default(),
+            serde_json::to_string(&self.validation_errors).unwrap_or_default()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> &'static str {
+        r#"{"type": "object", "properties": {"x": {"type": "number"}}, "required": ["x"]}"#
+    }
+
+    #[test]
+    fn register_rejects_invalid_json_schema() {
+        let mut registry = ToolRegistry::default();
+        let result = registry.register("bad_tool", "not json");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn validate_rejects_unknown_tool() {
+        let registry = ToolRegistry::default();
+        let result = registry.validate("missing_tool", "{}");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn validate_rejects_parameters_failing_schema() {
+        let mut registry = ToolRegistry::default();
+        registry.register("calc", schema()).unwrap();
+
+        let failure = registry.validate("calc", "{}").unwrap_err();
+        assert!(!failure.validation_errors.is_empty());
+    }
+
+    #[test]
+    fn validate_rejects_malformed_parameters_json() {
+        let mut registry = ToolRegistry::default();
+        registry.register("calc", schema()).unwrap();
+
+        let result = registry.validate("calc", "not json");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn validate_accepts_schema_conforming_parameters() {
+        let mut registry = ToolRegistry::default();
+        registry.register("calc", schema()).unwrap();
+
+        let parameters = registry.validate("calc", r#"{"x": 1}"#).unwrap();
+        assert_eq!(parameters["x"], 1);
+    }
+}